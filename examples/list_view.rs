@@ -1,5 +1,7 @@
 use eframe::App;
-use egui_widgets::list_view::{ListView, ListViewItem};
+use egui_widgets::list_view::{
+    Column, DetailLayout, ListView, ListViewItem, ListViewState, SortValue,
+};
 
 fn main() {
     let _ = eframe::run_native(
@@ -13,6 +15,13 @@ fn main() {
                         name: format!("{id:#04X}"),
                     })
                     .collect(),
+                state: ListViewState::default(),
+                large_list: (0..100_000)
+                    .map(|id| Item {
+                        id,
+                        name: format!("{id:#06X}"),
+                    })
+                    .collect(),
             }))
         }),
     );
@@ -20,13 +29,31 @@ fn main() {
 
 struct Application {
     list: Vec<Item>,
+    state: ListViewState,
+    large_list: Vec<Item>,
 }
 
 impl App for Application {
     fn ui(&mut self, ui: &mut egui::Ui, _frame: &mut eframe::Frame) {
         egui::CentralPanel::default().show_inside(ui, |ui| {
             ui.horizontal_top(|ui| {
-                ListView::new(self.list.iter(), ()).show(ui);
+                ListView::new(self.list.iter(), ())
+                    .hint_text("filter by name")
+                    .multi_select()
+                    .detail_layout(DetailLayout::Side)
+                    .state(&mut self.state)
+                    .on_activate(|item| println!("activated {}", item.name))
+                    .empty_text(egui::RichText::new("no matching items").weak())
+                    .show(ui);
+
+                if ui.button("clear selection").clicked() {
+                    self.state.clear_selection();
+                }
+
+                ListView::new(self.large_list.iter(), ())
+                    .title("Large List")
+                    .virtualized()
+                    .show(ui);
             });
         });
     }
@@ -59,7 +86,58 @@ impl ListViewItem for Item {
         });
     }
 
+    fn multi_selected_ui(ui: &mut egui::Ui, items: &[&Self], _data: Self::Data<'_>) {
+        ui.label(format!("{} items selected", items.len()));
+    }
+
     fn on_search(&self, text: &str, _data: Self::Data<'_>) -> bool {
         self.name.contains(text)
     }
+
+    fn search_ranges(&self, text: &str, _data: Self::Data<'_>) -> Vec<std::ops::Range<usize>> {
+        self.name
+            .match_indices(text)
+            .map(|(start, s)| start..start + s.len())
+            .collect()
+    }
+
+    fn groups(&self, _data: Self::Data<'_>) -> Vec<String> {
+        if self.id < 0x100 {
+            vec![format!("{:#04X}", self.id & !0xF)]
+        } else {
+            vec![]
+        }
+    }
+
+    fn columns() -> Vec<Column> {
+        vec![
+            Column::new("Name").width(80.0).sort_key("name"),
+            Column::new("Id").width(60.0).sort_key("id"),
+        ]
+    }
+
+    fn cell_ui(
+        &self,
+        ui: &mut egui::Ui,
+        column: usize,
+        label: &egui::WidgetText,
+        _data: Self::Data<'_>,
+    ) {
+        match column {
+            0 => ui.label(label.clone()),
+            _ => ui.label(self.id.to_string()),
+        };
+    }
+
+    fn sort_keys() -> &'static [&'static str] {
+        &["name", "id"]
+    }
+
+    fn sort_value(&self, key: &str, _data: Self::Data<'_>) -> SortValue {
+        match key {
+            "name" => SortValue::String(self.name.clone()),
+            "id" => SortValue::Number(self.id as f64),
+            _ => SortValue::None,
+        }
+    }
 }
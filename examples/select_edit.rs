@@ -1,5 +1,11 @@
 use eframe::App;
-use egui_widgets::SelectEdit;
+use egui_widgets::{
+    select_edit::{
+        AsyncCandidates, AsyncSelectEdit, CompletionEdit, LazySelectEdit, ListProvider, Matcher,
+        OpenOn, PopupHeight,
+    },
+    SelectEdit, SelectValue, TagEdit,
+};
 
 fn main() {
     let _ = eframe::run_native(
@@ -8,6 +14,23 @@ fn main() {
         Box::new(|_| {
             Ok(Box::new(Application {
                 text: Default::default(),
+                expression: Default::default(),
+                level: Level::Info,
+                tags: vec![],
+                category: String::new(),
+                categories: vec!["work".to_string(), "home".to_string()],
+                provider: ListProvider::new((0..200_000).map(|i| format!("symbol_{i}")).collect())
+                    .matcher(Matcher::IgnoreCase),
+                source: AsyncCandidates::new(|request| {
+                    std::thread::spawn(move || {
+                        std::thread::sleep(std::time::Duration::from_millis(500));
+                        let items = (0..100)
+                            .map(|i| format!("remote_{i}"))
+                            .filter(|item| item.contains(&request.query))
+                            .collect();
+                        request.respond(items);
+                    });
+                }),
             }))
         }),
     );
@@ -15,6 +38,21 @@ fn main() {
 
 struct Application {
     text: String,
+    expression: String,
+    level: Level,
+    tags: Vec<String>,
+    category: String,
+    categories: Vec<String>,
+    provider: ListProvider,
+    source: AsyncCandidates,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Level {
+    Debug,
+    Info,
+    Warn,
+    Error,
 }
 
 impl App for Application {
@@ -34,8 +72,93 @@ impl App for Application {
                         .enumerate()
                         .map(|c| c.1.to_string().repeat(c.0 + 1)),
                 )
+                .filter()
+                .history("letters", 10),
+            );
+
+            ui.add(
+                SelectEdit::new(
+                    &mut self.text,
+                    ('a'..='z')
+                        .enumerate()
+                        .map(|c| c.1.to_string().repeat(c.0 + 1)),
+                )
+                .matcher(Matcher::Fuzzy)
+                .popup_height(PopupHeight::Rows(5))
+                .open_on(OpenOn::Typing)
+                .keep_focus(),
+            );
+
+            ui.add(
+                SelectEdit::grouped(
+                    &mut self.text,
+                    [
+                        ("Vowels", vec!["a", "e", "i", "o", "u"]),
+                        ("Consonants", vec!["b", "c", "d", "f", "g"]),
+                    ],
+                )
                 .filter(),
             );
+
+            ui.add(
+                SelectEdit::new(&mut self.text, ["apple", "banana", "cherry"].into_iter())
+                    .filter()
+                    .row_ui(|ui, row| {
+                        ui.label("🍎");
+                        ui.label(row.label(ui));
+                        ui.label(egui::RichText::new("fruit").weak());
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            ui.label(row.text.len().to_string())
+                        });
+                    }),
+            );
+
+            ui.add(
+                SelectEdit::new(&mut self.text, ["apple", "banana", "cherry"].into_iter())
+                    .filter()
+                    .validator(|text| {
+                        if text.chars().count() > 10 {
+                            Err("At most 10 characters".to_string())
+                        } else {
+                            Ok(())
+                        }
+                    })
+                    .inline_error(),
+            );
+
+            ui.add(SelectValue::new(
+                &mut self.level,
+                [Level::Debug, Level::Info, Level::Warn, Level::Error].into_iter(),
+                |level| format!("{level:?}"),
+            ));
+
+            ui.add(
+                TagEdit::new(
+                    &mut self.tags,
+                    ["red", "green", "blue", "yellow", "black", "white"].into_iter(),
+                )
+                .allow_new(),
+            );
+
+            let mut created = None;
+            ui.add(
+                SelectEdit::new(&mut self.category, self.categories.iter())
+                    .filter()
+                    .on_create(|text| created = Some(text.to_string())),
+            );
+            self.categories.extend(created);
+
+            ui.add(LazySelectEdit::new(&mut self.text, &mut self.provider).max_results(100));
+
+            ui.add(AsyncSelectEdit::new(&mut self.text, &mut self.source));
+
+            ui.add(
+                CompletionEdit::new(
+                    &mut self.expression,
+                    ["sqrt", "sin", "cos", "tan", "abs", "floor", "ceil"].into_iter(),
+                )
+                .code_editor(),
+            );
         });
     }
 }
@@ -0,0 +1,52 @@
+use egui::{text::LayoutJob, Align, FontSelection, RichText, TextStyle, Ui, WidgetText};
+use std::ops::Range;
+
+/// 将`text`中位于`ranges`（字节范围）内的文字加粗并添加背景色，用于标记搜索匹配的部分
+pub(crate) fn highlight_text(ui: &Ui, text: &str, ranges: &[Range<usize>]) -> WidgetText {
+    let mut ranges = ranges
+        .iter()
+        .filter(|r| {
+            r.start < r.end
+                && r.end <= text.len()
+                && text.is_char_boundary(r.start)
+                && text.is_char_boundary(r.end)
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if ranges.is_empty() {
+        return text.into();
+    }
+
+    ranges.sort_by_key(|r| r.start);
+
+    let style = ui.style();
+    let font = FontSelection::Style(TextStyle::Button);
+    let mut job = LayoutJob::default();
+    let mut append = |s: &str, matched: bool| {
+        if s.is_empty() {
+            return;
+        }
+
+        let mut text = RichText::new(s);
+        if matched {
+            text = text.strong().background_color(style.visuals.code_bg_color);
+        }
+        text.append_to(&mut job, style, font.clone(), Align::Center);
+    };
+
+    let mut pos = 0;
+    for r in ranges {
+        let start = r.start.max(pos);
+        if start >= r.end {
+            continue;
+        }
+
+        append(&text[pos..start], false);
+        append(&text[start..r.end], true);
+        pos = r.end;
+    }
+    append(&text[pos..], false);
+
+    job.into()
+}
@@ -5,11 +5,17 @@ pub mod list_edit;
 pub mod list_view;
 
 #[cfg(feature = "select_edit")]
-mod select_edit;
+pub mod select_edit;
 
 #[cfg(feature = "option_value")]
 mod option_value;
 
+#[cfg(any(feature = "select_edit", feature = "list_view"))]
+mod highlight;
+
+#[cfg(any(feature = "select_edit", feature = "list_view"))]
+mod row;
+
 #[cfg(feature = "option_value")]
 pub use option_value::OptionValue;
 
@@ -17,4 +23,4 @@ pub use option_value::OptionValue;
 pub use egui_tracing as tracing;
 
 #[cfg(feature = "select_edit")]
-pub use select_edit::SelectEdit;
+pub use select_edit::{SelectEdit, SelectValue, TagEdit};
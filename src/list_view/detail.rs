@@ -0,0 +1,68 @@
+use egui::{vec2, Align, CursorIcon, Id, Layout, Sense, Ui};
+
+/// 选中项目的UI的显示位置
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DetailLayout {
+    /// 显示在列表下方
+    #[default]
+    Below,
+    /// 显示在列表右侧，可以拖动分隔线调整宽度
+    Side,
+    /// 显示在单独的窗口中。选中多个项目时窗口标题为列表的标题。
+    ///
+    /// 窗口关闭后，选中的项目改变时重新显示
+    Window,
+    /// 显示在列表下方可折叠的区域中，区域的标题为列表的标题
+    Collapsible,
+}
+
+/// 列表的最小宽度比例
+const MIN_RATIO: f32 = 0.1;
+
+/// 左右并排显示列表和选中项目的UI，列表所占的宽度比例保存在egui的内存中
+pub(super) fn side_by_side<R>(
+    ui: &mut Ui,
+    id: Id,
+    show_list: impl FnOnce(&mut Ui) -> R,
+    show_detail: impl FnOnce(&mut Ui, &R),
+) -> R {
+    let mut ratio: f32 = ui.data_mut(|d| d.get_persisted(id)).unwrap_or(0.5);
+
+    let resp = ui.horizontal_top(|ui| {
+        let total = ui.available_width();
+        let left = ui
+            .allocate_ui_with_layout(
+                vec2(total * ratio, ui.available_height()),
+                Layout::top_down(Align::Min),
+                |ui| {
+                    ui.set_width(total * ratio);
+                    show_list(ui)
+                },
+            )
+            .inner;
+
+        // 分隔线
+        let (rect, resp) = ui.allocate_exact_size(
+            vec2(ui.spacing().item_spacing.x, ui.min_rect().height()),
+            Sense::drag(),
+        );
+        let stroke = if resp.hovered() || resp.dragged() {
+            ui.visuals().widgets.hovered.fg_stroke
+        } else {
+            ui.visuals().widgets.noninteractive.bg_stroke
+        };
+        ui.painter().vline(rect.center().x, rect.y_range(), stroke);
+        if resp.hovered() || resp.dragged() {
+            ui.ctx().set_cursor_icon(CursorIcon::ResizeHorizontal);
+        }
+        if resp.dragged() && total > 0.0 {
+            ratio = (ratio + resp.drag_delta().x / total).clamp(MIN_RATIO, 1.0 - MIN_RATIO);
+        }
+
+        ui.vertical(|ui| show_detail(ui, &left));
+        left
+    });
+
+    ui.data_mut(|d| d.insert_persisted(id, ratio));
+    resp.inner
+}
@@ -1,4 +1,6 @@
-use egui::{Id, Ui};
+use super::Column;
+use egui::{Id, Ui, WidgetText};
+use std::{cmp::Ordering, ops::Range, time::SystemTime};
 
 pub trait ListViewItem {
     type Data<'a>: Copy;
@@ -14,6 +16,135 @@ pub trait ListViewItem {
     /// 被选中时显示的UI
     fn selected_ui(&self, ui: &mut Ui, _data: Self::Data<'_>);
 
+    /// 选中多个项目时显示的UI，可用于批量操作。默认不显示
+    fn multi_selected_ui(_ui: &mut Ui, _items: &[&Self], _data: Self::Data<'_>) {}
+
     /// 是否符合搜索条件
     fn on_search(&self, text: &str, _data: Self::Data<'_>) -> bool;
+
+    /// 搜索时`label`中需要高亮显示的字节范围。默认不高亮
+    fn search_ranges(&self, _text: &str, _data: Self::Data<'_>) -> Vec<Range<usize>> {
+        vec![]
+    }
+
+    /// 所属的分组，从外到内排列。默认不分组
+    fn groups(&self, _data: Self::Data<'_>) -> Vec<String> {
+        vec![]
+    }
+
+    /// 表格模式的列。默认不使用表格，只显示[`Self::label`]
+    fn columns() -> Vec<Column> {
+        vec![]
+    }
+
+    /// 表格中第`column`列的单元格。`label`为[`Self::label`]，搜索时高亮了匹配的部分。
+    /// 默认在第一列显示`label`
+    fn cell_ui(&self, ui: &mut Ui, column: usize, label: &WidgetText, _data: Self::Data<'_>) {
+        if column == 0 {
+            ui.label(label.clone());
+        }
+    }
+
+    /// 可以用于排序的键的名称。默认不能排序
+    fn sort_keys() -> &'static [&'static str] {
+        &[]
+    }
+
+    /// 项目在排序键`key`上的值
+    fn sort_value(&self, _key: &str, _data: Self::Data<'_>) -> SortValue {
+        SortValue::None
+    }
+}
+
+/// 用于排序的值。不同类型的值之间按声明的顺序排序
+#[derive(Debug, Clone)]
+pub enum SortValue {
+    None,
+    String(String),
+    Number(f64),
+    Date(SystemTime),
+}
+
+impl SortValue {
+    fn rank(&self) -> u8 {
+        match self {
+            SortValue::None => 0,
+            SortValue::String(_) => 1,
+            SortValue::Number(_) => 2,
+            SortValue::Date(_) => 3,
+        }
+    }
+}
+
+impl Ord for SortValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (SortValue::String(a), SortValue::String(b)) => a.cmp(b),
+            (SortValue::Number(a), SortValue::Number(b)) => a.total_cmp(b),
+            (SortValue::Date(a), SortValue::Date(b)) => a.cmp(b),
+            (a, b) => a.rank().cmp(&b.rank()),
+        }
+    }
+}
+
+impl PartialOrd for SortValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for SortValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for SortValue {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn sort_same_variant() {
+        let s = |v: &str| SortValue::String(v.to_string());
+        assert!(s("a") < s("b"));
+        assert_eq!(s("a"), s("a"));
+
+        assert!(SortValue::Number(-1.0) < SortValue::Number(2.5));
+        assert_eq!(SortValue::Number(0.0), SortValue::Number(0.0));
+        // NaN排在最后，并且等于自身，保证排序稳定
+        assert!(SortValue::Number(f64::INFINITY) < SortValue::Number(f64::NAN));
+        assert_eq!(SortValue::Number(f64::NAN), SortValue::Number(f64::NAN));
+
+        let t = SystemTime::UNIX_EPOCH;
+        assert!(SortValue::Date(t) < SortValue::Date(t + Duration::from_secs(1)));
+        assert_eq!(SortValue::None, SortValue::None);
+    }
+
+    #[test]
+    fn sort_across_variants() {
+        let mut values = vec![
+            SortValue::Date(SystemTime::UNIX_EPOCH),
+            SortValue::Number(1.0),
+            SortValue::None,
+            SortValue::String("b".into()),
+            SortValue::Number(-3.0),
+            SortValue::String("a".into()),
+        ];
+        values.sort();
+        assert_eq!(
+            values,
+            vec![
+                SortValue::None,
+                SortValue::String("a".into()),
+                SortValue::String("b".into()),
+                SortValue::Number(-3.0),
+                SortValue::Number(1.0),
+                SortValue::Date(SystemTime::UNIX_EPOCH),
+            ]
+        );
+        assert_ne!(SortValue::String("1".into()), SortValue::Number(1.0));
+    }
 }
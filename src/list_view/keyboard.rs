@@ -0,0 +1,108 @@
+use egui::{Event, EventFilter, Id, Key, Modifiers, Ui};
+
+/// 连续输入的字符之间的最大间隔，超过后重新开始查找
+const TYPE_AHEAD_TIMEOUT: f64 = 1.0;
+
+/// 列表获得焦点时按键的操作
+#[derive(Debug, Default)]
+pub(super) struct KeyAction {
+    /// 需要选中的行
+    pub row: Option<usize>,
+    /// 是否按下回车激活了当前行
+    pub activate: bool,
+}
+
+/// 处理方向键等移动选中行的按键。
+///
+/// `cursor`为当前选中的行，`page`为翻页时移动的行数
+pub(super) fn navigate(
+    ui: &Ui,
+    focus_id: Id,
+    cursor: Option<usize>,
+    rows: usize,
+    page: usize,
+) -> KeyAction {
+    ui.memory_mut(|m| {
+        m.set_focus_lock_filter(
+            focus_id,
+            EventFilter {
+                vertical_arrows: true,
+                ..Default::default()
+            },
+        )
+    });
+
+    if rows == 0 {
+        return KeyAction::default();
+    }
+
+    let last = rows - 1;
+    ui.input_mut(|i| {
+        let mut key = |key| i.consume_key(Modifiers::NONE, key);
+        let row = if key(Key::ArrowUp) {
+            Some(cursor.map_or(last, |c| c.saturating_sub(1)))
+        } else if key(Key::ArrowDown) {
+            Some(cursor.map_or(0, |c| (c + 1).min(last)))
+        } else if key(Key::Home) {
+            Some(0)
+        } else if key(Key::End) {
+            Some(last)
+        } else if key(Key::PageUp) {
+            Some(cursor.map_or(0, |c| c.saturating_sub(page)))
+        } else if key(Key::PageDown) {
+            Some(cursor.map_or(0, |c| (c + page).min(last)))
+        } else {
+            None
+        };
+
+        KeyAction {
+            row,
+            activate: cursor.is_some() && key(Key::Enter),
+        }
+    })
+}
+
+/// 根据输入的字符查找标签以其开头的行。
+///
+/// 在[`TYPE_AHEAD_TIMEOUT`]内连续输入的字符组成查找的前缀
+pub(super) fn type_ahead(
+    ui: &Ui,
+    buffer_id: Id,
+    cursor: Option<usize>,
+    rows: usize,
+    label: impl Fn(usize) -> String,
+) -> Option<usize> {
+    let typed = ui.input(|i| {
+        if i.modifiers.command {
+            return String::new();
+        }
+        i.events
+            .iter()
+            .filter_map(|e| match e {
+                Event::Text(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<String>()
+    });
+    if typed.is_empty() || rows == 0 {
+        return None;
+    }
+
+    let now = ui.input(|i| i.time);
+    let (mut prefix, last_time) = ui
+        .data(|d| d.get_temp::<(String, f64)>(buffer_id))
+        .unwrap_or_default();
+    // 开始新的查找时从下一行开始，继续输入时保留在当前行
+    let start = if now - last_time > TYPE_AHEAD_TIMEOUT {
+        prefix.clear();
+        cursor.map_or(0, |c| c + 1)
+    } else {
+        cursor.unwrap_or(0)
+    };
+    prefix.push_str(&typed.to_lowercase());
+    ui.data_mut(|d| d.insert_temp(buffer_id, (prefix.clone(), now)));
+
+    (start..rows)
+        .chain(0..start)
+        .find(|row| label(*row).to_lowercase().starts_with(&prefix))
+}
@@ -1,21 +1,80 @@
-use egui::{Id, Label, RichText, ScrollArea, TextEdit};
-pub use item::ListViewItem;
+use crate::{
+    highlight::highlight_text,
+    row::{row_height, scroll_to_row},
+};
+pub use detail::DetailLayout;
+use egui::{
+    Button, CollapsingHeader, ComboBox, Id, IdSet, Key, Label, Modifiers, RichText, ScrollArea,
+    Sense, TextEdit, WidgetText,
+};
+pub use item::{ListViewItem, SortValue};
 use parking_lot::RwLock;
+pub use state::{ListViewState, SortOrder};
+use std::sync::Arc;
+pub use table::Column;
 
+mod detail;
 mod item;
+mod keyboard;
+mod state;
+mod table;
+mod tree;
 
 static SEARCH_HINT_TEXT: RwLock<&'static str> = RwLock::new("search");
 
-/// 设置搜索框为空时显示的占位文字
+/// 设置搜索框为空时默认显示的占位文字。可以用[`ListView::hint_text`]为单个列表设置
 pub fn set_search_hint_text(text: &'static str) {
     *SEARCH_HINT_TEXT.write() = text;
 }
 
-#[derive(Debug)]
+/// 激活项目时调用，参数为被激活的项目
+pub type ActivateFn<'a, W> = Box<dyn FnMut(&'a W) + 'a>;
+
 pub struct ListView<'a, W: ListViewItem + 'a, L: Iterator<Item = &'a W>> {
     pub container: L,
     pub data: W::Data<'a>,
     pub height: f32,
+    /// 搜索框的占位文字，为`None`时使用[`set_search_hint_text`]设置的文字
+    pub hint_text: Option<WidgetText>,
+    /// 标题，为`None`时使用[`ListViewItem::title`]
+    pub title: Option<WidgetText>,
+    /// 没有可显示的项目时显示的文字
+    pub empty_text: Option<WidgetText>,
+    /// 是否允许按住Ctrl或Shift点击选中多个项目
+    pub multi_select: bool,
+    /// 由调用者保存的状态，为`None`时保存在egui的内存中
+    pub state: Option<&'a mut ListViewState>,
+    /// 是否只显示可见的行。所有行的高度必须相同
+    pub virtualized: bool,
+    /// 项目被修改时需要改变，用于判断缓存的搜索结果是否有效
+    pub generation: u64,
+    /// 按回车或双击激活项目时调用
+    pub on_activate: Option<ActivateFn<'a, W>>,
+    /// 选中项目的UI的显示位置
+    pub detail_layout: DetailLayout,
+}
+
+impl<'a, W, L> std::fmt::Debug for ListView<'a, W, L>
+where
+    W: ListViewItem + 'a,
+    L: Iterator<Item = &'a W> + std::fmt::Debug,
+    W::Data<'a>: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ListView")
+            .field("container", &self.container)
+            .field("data", &self.data)
+            .field("height", &self.height)
+            .field("hint_text", &self.hint_text)
+            .field("title", &self.title)
+            .field("empty_text", &self.empty_text)
+            .field("multi_select", &self.multi_select)
+            .field("state", &self.state)
+            .field("virtualized", &self.virtualized)
+            .field("generation", &self.generation)
+            .field("detail_layout", &self.detail_layout)
+            .finish_non_exhaustive()
+    }
 }
 
 impl<'a, W: ListViewItem + 'a, L: Iterator<Item = &'a W>> ListView<'a, W, L> {
@@ -24,6 +83,15 @@ impl<'a, W: ListViewItem + 'a, L: Iterator<Item = &'a W>> ListView<'a, W, L> {
             container,
             data,
             height: 200.0,
+            hint_text: None,
+            title: None,
+            empty_text: None,
+            multi_select: false,
+            state: None,
+            virtualized: false,
+            generation: 0,
+            on_activate: None,
+            detail_layout: DetailLayout::Below,
         }
     }
 
@@ -32,79 +100,587 @@ impl<'a, W: ListViewItem + 'a, L: Iterator<Item = &'a W>> ListView<'a, W, L> {
         self.height = height;
         self
     }
+
+    pub fn hint_text(mut self, hint_text: impl Into<WidgetText>) -> Self {
+        self.hint_text = Some(hint_text.into());
+        self
+    }
+
+    pub fn title(mut self, title: impl Into<WidgetText>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// 允许选中多个项目。
+    ///
+    /// 按住Ctrl点击切换项目是否选中，按住Shift点击选中一个范围，鼠标在列表上时按Ctrl+A选中所有项目
+    pub fn multi_select(mut self) -> Self {
+        self.multi_select = true;
+        self
+    }
+
+    /// 使用调用者保存的状态，可用于在程序中修改选中的项目，或在重启后恢复状态
+    pub fn state(mut self, state: &'a mut ListViewState) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    /// 只显示可见的行，用于项目数量很多的情况。
+    ///
+    /// 搜索结果会被缓存，只在搜索文字或[`Self::generation`]改变时重新搜索
+    pub fn virtualized(mut self) -> Self {
+        self.virtualized = true;
+        self
+    }
+
+    pub fn generation(mut self, generation: u64) -> Self {
+        self.generation = generation;
+        self
+    }
+
+    /// 列表获得焦点时按回车，或双击项目时调用
+    pub fn on_activate(mut self, on_activate: impl FnMut(&'a W) + 'a) -> Self {
+        self.on_activate = Some(Box::new(on_activate));
+        self
+    }
+
+    pub fn detail_layout(mut self, detail_layout: DetailLayout) -> Self {
+        self.detail_layout = detail_layout;
+        self
+    }
+
+    pub fn empty_text(mut self, empty_text: impl Into<WidgetText>) -> Self {
+        self.empty_text = Some(empty_text.into());
+        self
+    }
 }
 
 impl<'a, W: ListViewItem + 'a, L: Iterator<Item = &'a W>> ListView<'a, W, L> {
+    /// 显示列表，返回选中的项目。多选时返回第一个选中的项目
     pub fn show(self, ui: &mut egui::Ui) -> egui::InnerResponse<Option<&'a W>> {
-        let mut selected_item = None;
+        let resp = self.show_multi(ui);
+        egui::InnerResponse::new(resp.inner.into_iter().next(), resp.response)
+    }
 
+    /// 显示列表，按列表中的顺序返回所有选中的项目
+    pub fn show_multi(self, ui: &mut egui::Ui) -> egui::InnerResponse<Vec<&'a W>> {
         let mut resp = ui.vertical(|ui| {
             let ListView {
                 container,
                 data,
                 height,
+                hint_text,
+                title,
+                empty_text,
+                multi_select,
+                state: external_state,
+                virtualized,
+                generation,
+                mut on_activate,
+                detail_layout,
             } = self;
 
-            let resp = ui.group(|ui| {
-                let base_id = ui.auto_id_with("list viewer");
-                let search_id = base_id.with("search");
-                let selected_id = base_id.with("selected");
+            let base_id = ui.auto_id_with("list viewer");
+            let detail_title = title.clone().unwrap_or_else(|| W::title().into());
+            let show_list = |ui: &mut egui::Ui| {
+                let state_id = base_id.with("state");
+                let anchor_id = base_id.with("anchor");
+                let focus_id = base_id.with("focus");
+                let viewport_id = base_id.with("viewport");
 
-                let mut search: String = ui.data_mut(|d| d.get_temp(search_id)).unwrap_or_default();
-                let mut selected: Option<Id> =
-                    ui.data_mut(|d| d.get_temp(selected_id)).unwrap_or_default();
-                let old_selected = selected;
+                let ListViewState {
+                    mut search,
+                    mut selected,
+                    mut sort,
+                } = match &external_state {
+                    Some(state) => (*state).clone(),
+                    None => ui.data_mut(|d| d.get_temp(state_id)).unwrap_or_default(),
+                };
+                let mut anchor: Option<Id> =
+                    ui.data_mut(|d| d.get_temp(anchor_id)).unwrap_or_default();
+                let old_selected = selected.clone();
 
-                ui.horizontal_top(|ui| {
-                    ui.add(Label::new(RichText::new(W::title()).strong()));
-                    ui.add(TextEdit::singleline(&mut search).hint_text(*SEARCH_HINT_TEXT.read()));
-                });
+                let search_resp = ui
+                    .horizontal_top(|ui| {
+                        let title =
+                            title.unwrap_or_else(|| RichText::new(W::title()).strong().into());
+                        let hint_text =
+                            hint_text.unwrap_or_else(|| (*SEARCH_HINT_TEXT.read()).into());
+                        ui.add(Label::new(title));
+                        let search_resp = ui.add(
+                            TextEdit::singleline(&mut search)
+                                .id(base_id.with("search"))
+                                .hint_text(hint_text),
+                        );
+                        if !W::sort_keys().is_empty() {
+                            sort_ui(ui, base_id.with("sort"), W::sort_keys(), &mut sort);
+                        }
+                        search_resp
+                    })
+                    .inner;
 
                 ui.separator();
 
-                ScrollArea::vertical()
+                let row_height = row_height(ui);
+
+                // 表格模式下显示表头
+                let columns = W::columns();
+                let widths_id = base_id.with("column widths");
+                let mut widths = ui
+                    .data(|d| d.get_temp::<Vec<f32>>(widths_id))
+                    .filter(|w| w.len() == columns.len())
+                    .unwrap_or_else(|| columns.iter().map(|c| c.width).collect());
+                if !columns.is_empty() {
+                    table::header_ui(
+                        ui,
+                        base_id.with("resize"),
+                        &columns,
+                        &mut widths,
+                        row_height,
+                        &mut sort,
+                    );
+                    ui.data_mut(|d| d.insert_temp(widths_id, widths.clone()));
+                }
+
+                let items = container.collect::<Vec<_>>();
+                let filter = if virtualized {
+                    let cache_id = base_id.with("filter");
+                    let cache = ui.data(|d| d.get_temp::<Arc<Filter>>(cache_id));
+                    let filter = match cache {
+                        Some(cache) if cache.is_valid(generation, &search, &sort, items.len()) => {
+                            cache
+                        }
+                        cache => Arc::new(Filter::new(
+                            &items,
+                            data,
+                            generation,
+                            &search,
+                            &sort,
+                            cache.filter(|c| c.generation == generation),
+                        )),
+                    };
+                    ui.data_mut(|d| d.insert_temp(cache_id, filter.clone()));
+                    filter
+                } else {
+                    Arc::new(Filter::new(&items, data, generation, &search, &sort, None))
+                };
+                let Filter {
+                    ids,
+                    visible,
+                    groups,
+                    ..
+                } = &*filter;
+
+                // 搜索时展开所有分组，以显示匹配的项目
+                let collapsed_id = base_id.with("collapsed");
+                let mut collapsed: Vec<Id> =
+                    ui.data(|d| d.get_temp(collapsed_id)).unwrap_or_default();
+                let (rows, shown) = tree::flatten(
+                    groups,
+                    visible,
+                    base_id.with("group"),
+                    &collapsed,
+                    !search.is_empty(),
+                );
+
+                let visible_ids = shown.iter().map(|idx| ids[*idx]).collect::<Vec<_>>();
+                let row_pitch = row_height + ui.spacing().item_spacing.y;
+                // 上一帧列表的滚动位置和可见高度
+                let (offset, viewport): (f32, f32) =
+                    ui.data(|d| d.get_temp(viewport_id)).unwrap_or_default();
+
+                // 列表获得焦点时用键盘移动选中的行
+                let has_focus = ui.memory(|m| m.has_focus(focus_id));
+                let cursor = anchor.and_then(|a| visible_ids.iter().position(|v| *v == a));
+                let mut scroll_to = None;
+                let mut activated = None;
+                if has_focus {
+                    let page = ((viewport / row_pitch) as usize).max(1);
+                    let action = keyboard::navigate(ui, focus_id, cursor, shown.len(), page);
+                    let row = action.row.or_else(|| {
+                        keyboard::type_ahead(
+                            ui,
+                            base_id.with("type ahead"),
+                            cursor,
+                            shown.len(),
+                            |row| items[shown[row]].label(data),
+                        )
+                    });
+                    if let Some(row) = row {
+                        selected = vec![visible_ids[row]];
+                        anchor = Some(visible_ids[row]);
+                        scroll_to = Some(row);
+                    }
+                    if action.activate {
+                        activated = cursor;
+                    }
+                }
+
+                // 选中的项目可能很多，用集合判断是否选中
+                let selected_set = selected.iter().copied().collect::<IdSet>();
+                let mut clicked = None;
+                let mut toggled = None;
+                let mut show_row = |ui: &mut egui::Ui, row: usize| match rows[row] {
+                    tree::Row::Group {
+                        id,
+                        name,
+                        depth,
+                        open,
+                    } => {
+                        let icon = if open { "⏷" } else { "⏵" };
+                        let text = RichText::new(format!("{icon} {name}")).strong();
+                        if indented(ui, depth, |ui| ui.selectable_label(false, text)).clicked() {
+                            toggled = Some(id);
+                        }
+                    }
+                    tree::Row::Item { pos, depth } => {
+                        let item = items[shown[pos]];
+                        let checked = selected_set.contains(&ids[shown[pos]]);
+
+                        let label = item.label(data);
+                        let label = if search.is_empty() {
+                            label.into()
+                        } else {
+                            highlight_text(ui, &label, &item.search_ranges(&search, data))
+                        };
+                        let resp = if columns.is_empty() {
+                            indented(ui, depth, |ui| ui.selectable_label(checked, label))
+                        } else {
+                            let indent = depth as f32 * ui.spacing().indent;
+                            table::row_ui(ui, checked, &widths, row_height, indent, |ui, col| {
+                                item.cell_ui(ui, col, &label, data)
+                            })
+                        };
+                        if scroll_to == Some(pos) {
+                            resp.scroll_to_me(None);
+                        }
+                        if resp.clicked() {
+                            clicked = Some(pos);
+                        }
+                        if resp.double_clicked() {
+                            activated = Some(pos);
+                        }
+                    }
+                };
+
+                let mut scroll_area = ScrollArea::vertical()
                     .id_salt(base_id.with("list"))
                     .max_height(height)
-                    .auto_shrink([false, false])
-                    .show(ui, |ui| {
-                        for item in container {
-                            let id = item.id(data);
-                            let checked = selected == Some(id);
-
-                            if checked {
-                                selected_item = Some(item);
-                            }
+                    .auto_shrink([false, false]);
+                let output = if virtualized {
+                    let row = scroll_to.and_then(|pos| {
+                        rows.iter()
+                            .position(|r| matches!(r, tree::Row::Item { pos: p, .. } if *p == pos))
+                    });
+                    if let Some(row) = row {
+                        scroll_area =
+                            scroll_to_row(ui, scroll_area, row, row_height, (offset, viewport));
+                    }
+                    scroll_area.show_rows(ui, row_height, rows.len(), |ui, range| {
+                        range.for_each(|row| show_row(ui, row));
+                        show_empty_text(ui, visible, empty_text);
+                    })
+                } else {
+                    scroll_area.show(ui, |ui| {
+                        (0..rows.len()).for_each(|row| show_row(ui, row));
+                        show_empty_text(ui, visible, empty_text);
+                    })
+                };
+                ui.data_mut(|d| {
+                    d.insert_temp(
+                        viewport_id,
+                        (output.state.offset.y, output.inner_rect.height()),
+                    )
+                });
 
-                            if (search.is_empty() || item.on_search(&search, data))
-                                && ui.selectable_label(checked, item.label(data)).clicked()
-                                && !checked
-                            {
-                                selected = Some(id);
-                                selected_item = Some(item);
+                // 使列表可以获得焦点，点击项目时获得焦点
+                let list_resp = ui.interact(
+                    output.inner_rect,
+                    focus_id,
+                    Sense::focusable_noninteractive(),
+                );
+                if clicked.is_some() {
+                    list_resp.request_focus();
+                }
+
+                if let Some(idx) = clicked {
+                    let modifiers = ui.input(|i| i.modifiers);
+                    let id = visible_ids[idx];
+                    let anchor_idx = anchor.and_then(|a| visible_ids.iter().position(|v| *v == a));
+
+                    match anchor_idx {
+                        Some(a) if multi_select && modifiers.shift => {
+                            let mut selected_set = selected_set;
+                            if !modifiers.command {
+                                selected.clear();
+                                selected_set.clear();
+                            }
+                            for id in &visible_ids[a.min(idx)..=a.max(idx)] {
+                                if selected_set.insert(*id) {
+                                    selected.push(*id);
+                                }
                             }
                         }
-                    });
+                        _ if multi_select && modifiers.command => {
+                            if let Some(pos) = selected.iter().position(|s| *s == id) {
+                                selected.remove(pos);
+                            } else {
+                                selected.push(id);
+                            }
+                            anchor = Some(id);
+                        }
+                        _ => {
+                            selected = vec![id];
+                            anchor = Some(id);
+                        }
+                    }
+                }
 
-                if let Some(item) = selected_item {
-                    ui.separator();
-                    item.selected_ui(ui, data);
+                if let (Some(pos), Some(on_activate)) = (activated, &mut on_activate) {
+                    on_activate(items[shown[pos]]);
                 }
 
-                ui.data_mut(|d| {
-                    d.insert_temp(search_id, search);
-                    d.insert_temp(selected_id, selected);
-                });
+                if let Some(id) = toggled {
+                    if let Some(pos) = collapsed.iter().position(|c| *c == id) {
+                        collapsed.remove(pos);
+                    } else {
+                        collapsed.push(id);
+                    }
+                    ui.data_mut(|d| d.insert_temp(collapsed_id, collapsed));
+                }
+
+                let no_focus = ui.memory(|m| m.focused().is_none());
 
-                old_selected != selected
-            });
+                // 列表获得焦点，或没有控件获得焦点且鼠标在列表上时按Ctrl+F开始搜索
+                if (has_focus || no_focus && ui.rect_contains_pointer(output.inner_rect))
+                    && ui.input_mut(|i| i.consume_key(Modifiers::COMMAND, Key::F))
+                {
+                    search_resp.request_focus();
+                }
 
-            resp.inner
+                // 列表获得焦点，或没有控件获得焦点且鼠标在列表上时按Ctrl+A选中所有显示的项目
+                if multi_select
+                    && (has_focus || no_focus && ui.rect_contains_pointer(output.inner_rect))
+                    && ui.input_mut(|i| i.consume_key(Modifiers::COMMAND, Key::A))
+                {
+                    selected = visible_ids;
+                }
+
+                let selected_set = selected.iter().copied().collect::<IdSet>();
+                let selected_items = items
+                    .iter()
+                    .zip(ids)
+                    .filter(|(_, id)| selected_set.contains(id))
+                    .map(|(item, _)| *item)
+                    .collect::<Vec<_>>();
+
+                let changed = old_selected != selected;
+                let state = ListViewState {
+                    search,
+                    selected,
+                    sort,
+                };
+                ui.data_mut(|d| d.insert_temp(anchor_id, anchor));
+                match external_state {
+                    Some(external_state) => *external_state = state,
+                    None => {
+                        ui.data_mut(|d| d.insert_temp(state_id, state));
+                    }
+                }
+
+                (changed, selected_items)
+            };
+
+            let show_detail = |ui: &mut egui::Ui, items: &[&'a W]| match items {
+                [] => {}
+                [item] => item.selected_ui(ui, data),
+                items => W::multi_selected_ui(ui, items, data),
+            };
+
+            match detail_layout {
+                DetailLayout::Below => {
+                    ui.group(|ui| {
+                        let (changed, items) = show_list(ui);
+                        if !items.is_empty() {
+                            ui.separator();
+                            show_detail(ui, &items);
+                        }
+                        (changed, items)
+                    })
+                    .inner
+                }
+                DetailLayout::Side => {
+                    ui.group(|ui| {
+                        detail::side_by_side(ui, base_id.with("split"), show_list, |ui, r| {
+                            show_detail(ui, &r.1)
+                        })
+                    })
+                    .inner
+                }
+                DetailLayout::Window => {
+                    let (changed, items) = ui.group(show_list).inner;
+                    let window_id = base_id.with("detail window");
+                    // 关闭窗口后，选中的项目改变时重新显示
+                    let mut open = changed || ui.data(|d| d.get_temp(window_id)).unwrap_or(true);
+                    if !items.is_empty() {
+                        let title = match items.as_slice() {
+                            [item] => item.label(data).into(),
+                            _ => detail_title,
+                        };
+                        egui::Window::new(title)
+                            .id(window_id)
+                            .open(&mut open)
+                            .show(ui.ctx(), |ui| show_detail(ui, &items));
+                    }
+                    ui.data_mut(|d| d.insert_temp(window_id, open));
+                    (changed, items)
+                }
+                DetailLayout::Collapsible => {
+                    ui.group(|ui| {
+                        let (changed, items) = show_list(ui);
+                        if !items.is_empty() {
+                            ui.separator();
+                            CollapsingHeader::new(detail_title)
+                                .id_salt(base_id.with("detail"))
+                                .default_open(true)
+                                .show(ui, |ui| show_detail(ui, &items));
+                        }
+                        (changed, items)
+                    })
+                    .inner
+                }
+            }
         });
 
-        if resp.inner {
+        let (changed, selected_items) = resp.inner;
+        if changed {
             resp.response.mark_changed();
         }
 
-        egui::InnerResponse::new(selected_item, resp.response)
+        egui::InnerResponse::new(selected_items, resp.response)
+    }
+}
+
+fn show_empty_text(ui: &mut egui::Ui, visible: &[usize], empty_text: Option<WidgetText>) {
+    if let (true, Some(empty_text)) = (visible.is_empty(), empty_text) {
+        ui.add(Label::new(empty_text).selectable(false));
+    }
+}
+
+/// 按分组的深度缩进显示
+fn indented(
+    ui: &mut egui::Ui,
+    depth: usize,
+    add_contents: impl FnOnce(&mut egui::Ui) -> egui::Response,
+) -> egui::Response {
+    if depth == 0 {
+        add_contents(ui)
+    } else {
+        ui.horizontal(|ui| {
+            ui.add_space(depth as f32 * ui.spacing().indent);
+            add_contents(ui)
+        })
+        .inner
+    }
+}
+
+/// 选择排序的键和顺序
+fn sort_ui(ui: &mut egui::Ui, id: Id, keys: &[&str], sort: &mut Option<SortOrder>) {
+    let selected_key = sort.as_ref().map(|s| s.key.clone());
+    ComboBox::from_id_salt(id)
+        .selected_text(selected_key.as_deref().unwrap_or("-"))
+        .show_ui(ui, |ui| {
+            if ui.selectable_label(sort.is_none(), "-").clicked() {
+                *sort = None;
+            }
+            for key in keys {
+                if ui
+                    .selectable_label(selected_key.as_deref() == Some(*key), *key)
+                    .clicked()
+                {
+                    let ascending = sort.as_ref().is_none_or(|s| s.ascending);
+                    *sort = Some(SortOrder {
+                        key: key.to_string(),
+                        ascending,
+                    });
+                }
+            }
+        });
+
+    if let Some(sort) = sort {
+        let text = if sort.ascending { "⬆" } else { "⬇" };
+        if ui.add(Button::new(text).frame(false)).clicked() {
+            sort.ascending = !sort.ascending;
+        }
+    }
+}
+
+/// 所有项目的id，以及没有被搜索过滤掉并排序后的项目的序号
+struct Filter {
+    generation: u64,
+    search: String,
+    sort: Option<SortOrder>,
+    ids: Vec<Id>,
+    visible: Vec<usize>,
+    groups: Vec<tree::Entry>,
+}
+
+impl Filter {
+    /// 搜索项目。`cache`中的`generation`相同时重用其中的id
+    fn new<'a, W: ListViewItem>(
+        items: &[&'a W],
+        data: W::Data<'a>,
+        generation: u64,
+        search: &str,
+        sort: &Option<SortOrder>,
+        cache: Option<Arc<Filter>>,
+    ) -> Self {
+        let ids = match cache {
+            Some(cache) if cache.ids.len() == items.len() => cache.ids.clone(),
+            _ => items.iter().map(|item| item.id(data)).collect(),
+        };
+        let mut visible = items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| search.is_empty() || item.on_search(search, data))
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+
+        if let Some(sort) = sort {
+            let mut values = visible
+                .into_iter()
+                .map(|idx| (items[idx].sort_value(&sort.key, data), idx))
+                .collect::<Vec<_>>();
+            if sort.ascending {
+                values.sort_by(|(a, _), (b, _)| a.cmp(b));
+            } else {
+                values.sort_by(|(a, _), (b, _)| b.cmp(a));
+            }
+            visible = values.into_iter().map(|(_, idx)| idx).collect();
+        }
+
+        let groups = tree::build(items, data, &visible);
+
+        Self {
+            generation,
+            search: search.to_string(),
+            sort: sort.clone(),
+            groups,
+            ids,
+            visible,
+        }
+    }
+
+    fn is_valid(
+        &self,
+        generation: u64,
+        search: &str,
+        sort: &Option<SortOrder>,
+        len: usize,
+    ) -> bool {
+        self.generation == generation
+            && self.search == search
+            && self.sort == *sort
+            && self.ids.len() == len
     }
 }
@@ -0,0 +1,40 @@
+use egui::Id;
+
+/// 列表的搜索文字和选中的项目
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ListViewState {
+    pub search: String,
+    /// 选中的项目的[`super::ListViewItem::id`]
+    pub selected: Vec<Id>,
+    /// 为`None`时按原来的顺序显示
+    pub sort: Option<SortOrder>,
+}
+
+/// 列表的排序方式
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SortOrder {
+    /// [`super::ListViewItem::sort_keys`]中的一个
+    pub key: String,
+    pub ascending: bool,
+}
+
+impl ListViewState {
+    pub fn is_selected(&self, id: Id) -> bool {
+        self.selected.contains(&id)
+    }
+
+    /// 只选中`id`对应的项目
+    pub fn select(&mut self, id: Id) {
+        self.selected = vec![id];
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selected.clear();
+    }
+}
@@ -0,0 +1,137 @@
+use super::SortOrder;
+use crate::row::selectable_row;
+use egui::{
+    vec2, Align, CursorIcon, Id, Label, Layout, Response, Sense, Ui, UiBuilder, WidgetText,
+};
+
+/// 表格的一列
+#[derive(Debug, Clone)]
+pub struct Column {
+    pub title: WidgetText,
+    /// 初始宽度
+    pub width: f32,
+    /// 是否可以拖动调整宽度
+    pub resizable: bool,
+    /// 点击标题时使用的排序键，见[`super::ListViewItem::sort_keys`]
+    pub sort_key: Option<&'static str>,
+}
+
+impl Column {
+    pub fn new(title: impl Into<WidgetText>) -> Self {
+        Self {
+            title: title.into(),
+            width: 100.0,
+            resizable: true,
+            sort_key: None,
+        }
+    }
+
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    pub fn sort_key(mut self, sort_key: &'static str) -> Self {
+        self.sort_key = Some(sort_key);
+        self
+    }
+}
+
+/// 列的最小宽度
+const MIN_WIDTH: f32 = 20.0;
+
+/// 显示表头。拖动列的右边缘调整宽度，点击可排序的列切换排序方式
+pub(super) fn header_ui(
+    ui: &mut Ui,
+    id: Id,
+    columns: &[Column],
+    widths: &mut [f32],
+    height: f32,
+    sort: &mut Option<SortOrder>,
+) {
+    ui.horizontal(|ui| {
+        for (col, column) in columns.iter().enumerate() {
+            let sense = if column.sort_key.is_some() {
+                Sense::click()
+            } else {
+                Sense::hover()
+            };
+            let (rect, resp) = ui.allocate_exact_size(vec2(widths[col], height), sense);
+
+            let mut cell = ui.new_child(
+                UiBuilder::new()
+                    .max_rect(rect)
+                    .layout(Layout::left_to_right(Align::Center)),
+            );
+            cell.set_clip_rect(rect.intersect(ui.clip_rect()));
+            if let (Some(key), Some(sort)) = (column.sort_key, &sort) {
+                if sort.key == key {
+                    cell.label(if sort.ascending { "⬆" } else { "⬇" });
+                }
+            }
+            cell.add(
+                Label::new(column.title.clone().strong())
+                    .truncate()
+                    .selectable(false),
+            );
+
+            if let (Some(key), true) = (column.sort_key, resp.clicked()) {
+                *sort = match sort.take() {
+                    Some(s) if s.key == key => Some(SortOrder {
+                        ascending: !s.ascending,
+                        ..s
+                    }),
+                    _ => Some(SortOrder {
+                        key: key.to_string(),
+                        ascending: true,
+                    }),
+                };
+            }
+
+            if column.resizable {
+                let handle = egui::Rect::from_x_y_ranges(
+                    rect.right() - 2.0..=rect.right() + ui.spacing().item_spacing.x,
+                    rect.y_range(),
+                );
+                let resp = ui.interact(handle, id.with(col), Sense::drag());
+                if resp.hovered() || resp.dragged() {
+                    ui.ctx().set_cursor_icon(CursorIcon::ResizeHorizontal);
+                }
+                if resp.dragged() {
+                    widths[col] = (widths[col] + resp.drag_delta().x).max(MIN_WIDTH);
+                }
+            }
+        }
+    });
+}
+
+/// 显示可选择的表格行，`add_cell`的参数为列的序号
+pub(super) fn row_ui(
+    ui: &mut Ui,
+    selected: bool,
+    widths: &[f32],
+    height: f32,
+    indent: f32,
+    mut add_cell: impl FnMut(&mut Ui, usize),
+) -> Response {
+    selectable_row(ui, selected, |ui| {
+        for (col, width) in widths.iter().enumerate() {
+            let (rect, _) = ui.allocate_exact_size(vec2(*width, height), Sense::hover());
+            let mut cell = ui.new_child(
+                UiBuilder::new()
+                    .max_rect(rect)
+                    .layout(Layout::left_to_right(Align::Center)),
+            );
+            cell.set_clip_rect(rect.intersect(ui.clip_rect()));
+            if col == 0 {
+                cell.add_space(indent);
+            }
+            add_cell(&mut cell, col);
+        }
+    })
+}
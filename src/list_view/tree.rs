@@ -0,0 +1,263 @@
+use super::ListViewItem;
+use egui::Id;
+use std::collections::HashMap;
+
+/// 按[`ListViewItem::groups`]分组后的项目
+#[derive(Debug)]
+pub(super) enum Entry {
+    Group(String, Vec<Entry>),
+    /// 项目的序号
+    Item(usize),
+}
+
+/// 列表中显示的一行
+#[derive(Debug)]
+pub(super) enum Row<'e> {
+    Group {
+        id: Id,
+        name: &'e str,
+        depth: usize,
+        open: bool,
+    },
+    Item {
+        /// 在显示的项目中的序号
+        pos: usize,
+        depth: usize,
+    },
+}
+
+/// 按项目第一次出现的顺序分组。没有项目属于任何分组时返回空列表
+pub(super) fn build<'a, W: ListViewItem>(
+    items: &[&'a W],
+    data: W::Data<'a>,
+    visible: &[usize],
+) -> Vec<Entry> {
+    let mut root = Level::default();
+    let mut grouped = false;
+
+    for idx in visible {
+        let mut current = &mut root;
+        for group in items[*idx].groups(data) {
+            grouped = true;
+            current = current.group(group);
+        }
+        current.entries.push(Node::Item(*idx));
+    }
+
+    if grouped {
+        root.into_entries()
+    } else {
+        Vec::new()
+    }
+}
+
+/// 构建分组时的一层，记录分组名称对应的序号
+#[derive(Default)]
+struct Level {
+    entries: Vec<Node>,
+    groups: HashMap<String, usize>,
+}
+
+enum Node {
+    Group(String, Level),
+    Item(usize),
+}
+
+impl Level {
+    /// 名称为`name`的子分组，不存在时添加到末尾
+    fn group(&mut self, name: String) -> &mut Level {
+        let pos = *self.groups.entry(name).or_insert_with_key(|name| {
+            self.entries
+                .push(Node::Group(name.clone(), Level::default()));
+            self.entries.len() - 1
+        });
+        let Node::Group(_, level) = &mut self.entries[pos] else {
+            unreachable!()
+        };
+        level
+    }
+
+    fn into_entries(self) -> Vec<Entry> {
+        self.entries
+            .into_iter()
+            .map(|node| match node {
+                Node::Group(name, level) => Entry::Group(name, level.into_entries()),
+                Node::Item(idx) => Entry::Item(idx),
+            })
+            .collect()
+    }
+}
+
+/// 展开分组，返回显示的行和按显示顺序排列的项目序号。
+///
+/// `collapsed`中的分组不显示其中的项目，`force_open`为`true`时展开所有分组
+pub(super) fn flatten<'e>(
+    entries: &'e [Entry],
+    visible: &[usize],
+    group_id: Id,
+    collapsed: &[Id],
+    force_open: bool,
+) -> (Vec<Row<'e>>, Vec<usize>) {
+    if entries.is_empty() {
+        let rows = (0..visible.len())
+            .map(|pos| Row::Item { pos, depth: 0 })
+            .collect();
+        return (rows, visible.to_vec());
+    }
+
+    let mut rows = Vec::new();
+    let mut shown = Vec::new();
+    flatten_into(
+        entries, 0, group_id, collapsed, force_open, &mut rows, &mut shown,
+    );
+    (rows, shown)
+}
+
+fn flatten_into<'e>(
+    entries: &'e [Entry],
+    depth: usize,
+    parent_id: Id,
+    collapsed: &[Id],
+    force_open: bool,
+    rows: &mut Vec<Row<'e>>,
+    shown: &mut Vec<usize>,
+) {
+    for entry in entries {
+        match entry {
+            Entry::Group(name, children) => {
+                let id = parent_id.with(name);
+                let open = force_open || !collapsed.contains(&id);
+                rows.push(Row::Group {
+                    id,
+                    name,
+                    depth,
+                    open,
+                });
+                if open {
+                    flatten_into(children, depth + 1, id, collapsed, force_open, rows, shown);
+                }
+            }
+            Entry::Item(idx) => {
+                rows.push(Row::Item {
+                    pos: shown.len(),
+                    depth,
+                });
+                shown.push(*idx);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::Ui;
+
+    struct Item(&'static str, &'static [&'static str]);
+
+    impl ListViewItem for Item {
+        type Data<'a> = ();
+
+        fn title() -> &'static str {
+            "Item"
+        }
+
+        fn label(&self, _data: ()) -> String {
+            self.0.to_string()
+        }
+
+        fn id(&self, _data: ()) -> Id {
+            Id::new(self.0)
+        }
+
+        fn selected_ui(&self, _ui: &mut Ui, _data: ()) {}
+
+        fn on_search(&self, text: &str, _data: ()) -> bool {
+            self.0.contains(text)
+        }
+
+        fn groups(&self, _data: ()) -> Vec<String> {
+            self.1.iter().map(|g| g.to_string()).collect()
+        }
+    }
+
+    const ITEMS: &[Item] = &[
+        Item("a", &["x", "y"]),
+        Item("b", &[]),
+        Item("c", &["z"]),
+        Item("d", &["x"]),
+        Item("e", &["x", "y"]),
+    ];
+
+    fn items() -> Vec<&'static Item> {
+        ITEMS.iter().collect()
+    }
+
+    /// 以`名称(子项)`和序号的形式表示分组，便于比较
+    fn describe(entries: &[Entry]) -> String {
+        entries
+            .iter()
+            .map(|e| match e {
+                Entry::Group(name, children) => format!("{name}({})", describe(children)),
+                Entry::Item(idx) => idx.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn describe_rows(rows: &[Row]) -> String {
+        rows.iter()
+            .map(|r| match r {
+                Row::Group {
+                    name, depth, open, ..
+                } => format!("{depth}{name}{}", if *open { "+" } else { "-" }),
+                Row::Item { pos, depth } => format!("{depth}#{pos}"),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn build_groups() {
+        let entries = build(&items(), (), &[0, 1, 2, 3, 4]);
+        assert_eq!(describe(&entries), "x(y(0 4) 3) 1 z(2)");
+
+        // 只包含显示的项目，顺序按显示的顺序
+        let entries = build(&items(), (), &[3, 2, 0]);
+        assert_eq!(describe(&entries), "x(3 y(0)) z(2)");
+    }
+
+    #[test]
+    fn build_ungrouped() {
+        assert!(build(&items(), (), &[1]).is_empty());
+        assert!(build(&items(), (), &[]).is_empty());
+    }
+
+    #[test]
+    fn flatten_ungrouped() {
+        let (rows, shown) = flatten(&[], &[4, 1], Id::NULL, &[], false);
+        assert_eq!(describe_rows(&rows), "0#0 0#1");
+        assert_eq!(shown, vec![4, 1]);
+    }
+
+    #[test]
+    fn flatten_groups() {
+        let visible = [0, 1, 2, 3, 4];
+        let entries = build(&items(), (), &visible);
+        let id = Id::new("groups");
+
+        let (rows, shown) = flatten(&entries, &visible, id, &[], false);
+        assert_eq!(describe_rows(&rows), "0x+ 1y+ 2#0 2#1 1#2 0#3 0z+ 1#4");
+        assert_eq!(shown, vec![0, 4, 3, 1, 2]);
+
+        // 折叠的分组不显示其中的项目
+        let collapsed = [id.with("x").with("y"), id.with("z")];
+        let (rows, shown) = flatten(&entries, &visible, id, &collapsed, false);
+        assert_eq!(describe_rows(&rows), "0x+ 1y- 1#0 0#1 0z-");
+        assert_eq!(shown, vec![3, 1]);
+
+        let (rows, shown) = flatten(&entries, &visible, id, &collapsed, true);
+        assert_eq!(rows.len(), 8);
+        assert_eq!(shown, vec![0, 4, 3, 1, 2]);
+    }
+}
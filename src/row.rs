@@ -0,0 +1,61 @@
+use egui::{epaint::RectShape, Response, ScrollArea, Sense, Shape, TextStyle, Ui, UiBuilder};
+
+/// 可选择的行的高度，与`selectable_label`相同
+pub(crate) fn row_height(ui: &Ui) -> f32 {
+    ui.spacing()
+        .interact_size
+        .y
+        .max(ui.text_style_height(&TextStyle::Button) + 2.0 * ui.spacing().button_padding.y)
+}
+
+/// 显示自定义内容的可选择行，内容从左到右排列
+pub(crate) fn selectable_row(
+    ui: &mut Ui,
+    selected: bool,
+    add_contents: impl FnOnce(&mut Ui),
+) -> Response {
+    let bg = ui.painter().add(Shape::Noop);
+
+    let resp = ui
+        .scope_builder(UiBuilder::new().sense(Sense::click()), |ui| {
+            // 可选择的文字会拦截对行的点击
+            ui.style_mut().interaction.selectable_labels = false;
+            ui.horizontal(add_contents);
+        })
+        .response;
+
+    if selected || resp.hovered() || resp.highlighted() {
+        let visuals = ui.style().interact_selectable(&resp, selected);
+        ui.painter().set(
+            bg,
+            RectShape::filled(
+                resp.rect.expand(visuals.expansion),
+                visuals.corner_radius,
+                visuals.weak_bg_fill,
+            ),
+        );
+    }
+
+    resp
+}
+
+/// 使`scroll_area`滚动到第`row`行。
+///
+/// 使用`show_rows`时该行可能没有被显示，无法使用`scroll_to_me`，
+/// 因此根据上一帧的滚动位置`offset`和可见高度`viewport`计算新的滚动位置
+pub(crate) fn scroll_to_row(
+    ui: &Ui,
+    scroll_area: ScrollArea,
+    row: usize,
+    row_height: f32,
+    (offset, viewport): (f32, f32),
+) -> ScrollArea {
+    let row_top = row as f32 * (row_height + ui.spacing().item_spacing.y);
+    if row_top < offset {
+        scroll_area.vertical_scroll_offset(row_top)
+    } else if row_top + row_height > offset + viewport {
+        scroll_area.vertical_scroll_offset(row_top + row_height - viewport)
+    } else {
+        scroll_area
+    }
+}
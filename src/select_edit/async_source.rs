@@ -0,0 +1,134 @@
+use super::SelectEdit;
+use egui::{Context, Response, Ui, Widget};
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+type Reply = (u64, Vec<String>);
+
+/// 在其他线程中获取候选项。
+///
+/// 搜索文字改变时调用创建时传入的方法发起查询，查询结果通过[`QueryRequest::respond`]返回。
+/// 旧的查询返回的结果会被丢弃
+pub struct AsyncCandidates {
+    request: Box<dyn FnMut(QueryRequest)>,
+    sender: Sender<Reply>,
+    receiver: Receiver<Reply>,
+    generation: u64,
+    query: Option<String>,
+    results: Option<Vec<String>>,
+}
+
+/// 一次候选项查询
+#[derive(Debug)]
+pub struct QueryRequest {
+    pub query: String,
+    generation: u64,
+    sender: Sender<Reply>,
+    ctx: Context,
+}
+
+impl AsyncCandidates {
+    pub fn new(request: impl FnMut(QueryRequest) + 'static) -> Self {
+        let (sender, receiver) = channel();
+        Self {
+            request: Box::new(request),
+            sender,
+            receiver,
+            generation: 0,
+            query: None,
+            results: None,
+        }
+    }
+
+    /// 获取`query`的查询结果。查询结果尚未返回时返回`None`
+    pub fn query(&mut self, ctx: &Context, query: &str) -> Option<&[String]> {
+        if self.query.as_deref() != Some(query) {
+            self.generation += 1;
+            self.query = Some(query.to_string());
+            self.results = None;
+            (self.request)(QueryRequest {
+                query: query.to_string(),
+                generation: self.generation,
+                sender: self.sender.clone(),
+                ctx: ctx.clone(),
+            });
+        }
+
+        while let Ok((generation, items)) = self.receiver.try_recv() {
+            if generation == self.generation {
+                self.results = Some(items);
+            }
+        }
+
+        self.results.as_deref()
+    }
+
+    /// 是否有尚未返回结果的查询
+    pub fn is_pending(&self) -> bool {
+        self.query.is_some() && self.results.is_none()
+    }
+
+    /// 清除查询结果，下次显示时重新查询
+    pub fn reset(&mut self) {
+        self.query = None;
+        self.results = None;
+    }
+}
+
+impl QueryRequest {
+    /// 返回查询结果，并请求重绘界面
+    pub fn respond(self, items: Vec<String>) {
+        if self.sender.send((self.generation, items)).is_ok() {
+            self.ctx.request_repaint();
+        }
+    }
+}
+
+/// 从[`AsyncCandidates`]获取候选项的选择框。查询结果返回前弹出框中显示加载动画
+pub struct AsyncSelectEdit<'a> {
+    pub text: &'a mut String,
+    pub source: &'a mut AsyncCandidates,
+    pub hint_text: Option<&'a str>,
+}
+
+impl<'a> AsyncSelectEdit<'a> {
+    pub fn new(text: &'a mut String, source: &'a mut AsyncCandidates) -> Self {
+        Self {
+            text,
+            source,
+            hint_text: None,
+        }
+    }
+
+    pub fn hint_text(self, hint_text: impl Into<Option<&'a str>>) -> Self {
+        Self {
+            hint_text: hint_text.into(),
+            ..self
+        }
+    }
+}
+
+impl Widget for AsyncSelectEdit<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let AsyncSelectEdit {
+            text,
+            source,
+            hint_text,
+        } = self;
+
+        let ctx = ui.ctx().clone();
+        let results = source.query(&ctx, text);
+        let loading = results.is_none();
+
+        let resp = SelectEdit::new(text, results.unwrap_or_default().iter())
+            .hint_text(hint_text)
+            .loading(loading)
+            .ui(ui);
+
+        // 文本在这一帧被修改时立即发起新的查询，下一帧丢弃旧的结果并显示加载动画
+        if source.query(&ctx, text).is_none() && resp.changed() {
+            ctx.request_repaint();
+        }
+
+        resp
+    }
+}
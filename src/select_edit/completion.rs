@@ -0,0 +1,402 @@
+use super::{match_candidates, navigate, Candidate, Matcher, SelectEvent, SelectOutput};
+use crate::highlight::highlight_text;
+use egui::{
+    text::{CCursor, CCursorRange},
+    text_edit::TextEditState,
+    Id, InnerResponse, Key, Popup, PopupCloseBehavior, RectAlign, ScrollArea, SetOpenCommand,
+    TextEdit, Ui, Widget,
+};
+use std::ops::Range;
+
+/// 多行文本的自动补全。
+///
+/// 只使用光标所在的单词筛选候选项，弹出框显示在光标处，选中候选项时只替换该单词
+pub struct CompletionEdit<'a, S, L>
+where
+    S: ToString,
+    L: Iterator<Item = S>,
+{
+    pub text: &'a mut String,
+    pub iter: L,
+    pub matcher: Matcher<'a>,
+    pub hint_text: Option<&'a str>,
+    /// 组成单词的字符，默认为字母、数字和下划线
+    pub is_token_char: fn(char) -> bool,
+    /// 单词至少有多少个字符时才显示候选项
+    pub min_token_len: usize,
+    pub desired_rows: usize,
+    /// 是否使用等宽字体
+    pub code_editor: bool,
+    pub popup_max_height: f32,
+}
+
+impl<'a, S, L> CompletionEdit<'a, S, L>
+where
+    S: ToString,
+    L: Iterator<Item = S>,
+{
+    pub fn new(text: &'a mut String, iter: L) -> Self {
+        Self {
+            text,
+            iter,
+            matcher: Matcher::Prefix,
+            hint_text: None,
+            is_token_char: |c| c.is_alphanumeric() || c == '_',
+            min_token_len: 1,
+            desired_rows: 4,
+            code_editor: false,
+            popup_max_height: 150.0,
+        }
+    }
+
+    /// 筛选候选项时使用的匹配方式。默认为[`Matcher::Prefix`]
+    pub fn matcher(self, matcher: Matcher<'a>) -> Self {
+        Self { matcher, ..self }
+    }
+
+    pub fn hint_text(self, hint_text: impl Into<Option<&'a str>>) -> Self {
+        Self {
+            hint_text: hint_text.into(),
+            ..self
+        }
+    }
+
+    pub fn is_token_char(self, is_token_char: fn(char) -> bool) -> Self {
+        Self {
+            is_token_char,
+            ..self
+        }
+    }
+
+    pub fn min_token_len(self, min_token_len: usize) -> Self {
+        Self {
+            min_token_len,
+            ..self
+        }
+    }
+
+    pub fn desired_rows(self, desired_rows: usize) -> Self {
+        Self {
+            desired_rows,
+            ..self
+        }
+    }
+
+    pub fn code_editor(self) -> Self {
+        Self {
+            code_editor: true,
+            ..self
+        }
+    }
+
+    pub fn popup_max_height(self, popup_max_height: f32) -> Self {
+        Self {
+            popup_max_height,
+            ..self
+        }
+    }
+
+    /// 显示文本框，返回这一帧中发生的事件
+    pub fn show(self, ui: &mut Ui) -> InnerResponse<SelectOutput> {
+        let text_id = ui.next_auto_id();
+        let popup_id = ui.auto_id_with(module_path!()).with("completion popup");
+        let highlight_id = popup_id.with("highlight");
+
+        let items = self.iter.map(|s| s.to_string()).collect::<Vec<_>>();
+        let was_open = Popup::is_id_open(ui.ctx(), popup_id);
+        let token_at = |text: &str, caret: Option<usize>| {
+            caret
+                .map(|caret| Token::new(text, caret, self.is_token_char))
+                .filter(|token| token.query(text).chars().count() >= self.min_token_len)
+        };
+
+        let mut events = Vec::new();
+        let mut changed = false;
+        let mut picked = None;
+        let mut highlight = ui
+            .data(|d| d.get_temp::<Option<usize>>(highlight_id))
+            .flatten();
+        let mut scroll_to_highlight = false;
+
+        // 在文本框处理按键之前用上一帧的光标位置选择候选项
+        if was_open {
+            let token = token_at(self.text, caret(ui, text_id));
+            let candidates = token
+                .as_ref()
+                .map(|t| match_candidates(&self.matcher, t.query(self.text), &items))
+                .unwrap_or_default();
+            highlight = highlight.filter(|h| *h < candidates.len());
+            if let (Some(h), Some(token)) = (
+                navigate(
+                    ui,
+                    text_id,
+                    popup_id,
+                    candidates.len(),
+                    &mut highlight,
+                    &mut scroll_to_highlight,
+                ),
+                &token,
+            ) {
+                token.replace(ui, text_id, self.text, &candidates[h].text);
+                picked = Some(candidates[h].index);
+                events.push(SelectEvent::Picked(candidates[h].index));
+                changed = true;
+                Popup::close_id(ui.ctx(), popup_id);
+                highlight = None;
+            }
+        }
+
+        let mut text_edit = TextEdit::multiline(self.text)
+            .id(text_id)
+            .desired_rows(self.desired_rows);
+        if let Some(hint_text) = self.hint_text {
+            text_edit = text_edit.hint_text(hint_text);
+        }
+        if self.code_editor {
+            text_edit = text_edit.code_editor();
+        }
+        let output = text_edit.show(ui);
+        let mut resp = output.response.response;
+        if resp.changed() {
+            changed = true;
+            events.push(SelectEvent::Typed);
+        }
+
+        // 失去焦点后文本框的状态中仍保存着光标位置，点击候选项时需要用到
+        let caret_char = output.state.cursor.char_range().map(|r| r.primary);
+        let token = token_at(self.text, caret_char.map(|c| c.index));
+        let candidates = token
+            .as_ref()
+            .map(|t| match_candidates(&self.matcher, t.query(self.text), &items))
+            .unwrap_or_default();
+
+        // 输入时打开弹出框，光标离开单词或没有候选项时关闭
+        let open_command = if candidates.is_empty() {
+            Some(SetOpenCommand::Bool(false))
+        } else if resp.changed() && picked.is_none() {
+            highlight = Some(0);
+            Some(SetOpenCommand::Bool(true))
+        } else {
+            None
+        };
+        let highlight = highlight.filter(|h| *h < candidates.len());
+
+        // 弹出框显示在光标的下方
+        let mut anchor = caret_char
+            .map(|c| {
+                output
+                    .galley
+                    .pos_from_cursor(c)
+                    .translate(output.galley_pos.to_vec2())
+            })
+            .unwrap_or(resp.rect);
+        if let Some(to_global) = ui.ctx().layer_transform_to_global(ui.layer_id()) {
+            anchor = to_global * anchor;
+        }
+
+        let mut clicked = None;
+        Popup::menu(&resp)
+            .id(popup_id)
+            .anchor(anchor)
+            .open_memory(open_command)
+            .align(RectAlign::BOTTOM_START)
+            .align_alternatives(&[RectAlign::TOP_START])
+            .close_behavior(PopupCloseBehavior::IgnoreClicks)
+            .show(|ui| {
+                ScrollArea::vertical()
+                    .max_height(self.popup_max_height)
+                    .show(ui, |ui| {
+                        for (idx, candidate) in candidates.iter().enumerate() {
+                            let mut r = ui.selectable_label(
+                                false,
+                                highlight_text(ui, &candidate.text, &candidate.ranges),
+                            );
+                            if highlight == Some(idx) {
+                                r = r.highlight();
+                                if scroll_to_highlight {
+                                    r.scroll_to_me(None);
+                                }
+                            }
+                            if r.clicked() {
+                                clicked = Some(idx);
+                            }
+                        }
+                    });
+            });
+
+        if let (Some(idx), Some(token)) = (clicked, &token) {
+            let Candidate { index, text, .. } = &candidates[idx];
+            token.replace(ui, text_id, self.text, text);
+            picked = Some(*index);
+            events.push(SelectEvent::Picked(*index));
+            changed = true;
+            Popup::close_id(ui.ctx(), popup_id);
+            resp.request_focus();
+        }
+
+        if ui.input(|i| i.key_pressed(Key::Escape)) || resp.lost_focus() && clicked.is_none() {
+            Popup::close_id(ui.ctx(), popup_id);
+        }
+
+        let is_open = Popup::is_id_open(ui.ctx(), popup_id);
+        if is_open {
+            ui.data_mut(|d| d.insert_temp(highlight_id, highlight));
+        } else {
+            ui.data_mut(|d| d.remove::<Option<usize>>(highlight_id));
+        }
+
+        if is_open != was_open {
+            events.push(if is_open {
+                SelectEvent::PopupOpened
+            } else {
+                SelectEvent::PopupClosed
+            });
+        }
+
+        if changed {
+            resp.mark_changed();
+        }
+
+        InnerResponse::new(
+            SelectOutput {
+                events,
+                picked,
+                error: None,
+            },
+            resp,
+        )
+    }
+}
+
+impl<'a, S, L> Widget for CompletionEdit<'a, S, L>
+where
+    S: ToString,
+    L: Iterator<Item = S>,
+{
+    fn ui(self, ui: &mut Ui) -> egui::Response {
+        self.show(ui).response
+    }
+}
+
+/// 文本框中光标的字符位置
+fn caret(ui: &Ui, text_id: Id) -> Option<usize> {
+    TextEditState::load(ui.ctx(), text_id)
+        .and_then(|state| state.cursor.char_range())
+        .map(|range| range.primary.index)
+}
+
+/// 光标所在的单词
+struct Token {
+    /// 单词的字节范围
+    range: Range<usize>,
+    /// 光标的字节位置
+    caret: usize,
+}
+
+impl Token {
+    fn new(text: &str, caret: usize, is_token_char: fn(char) -> bool) -> Self {
+        let caret = text
+            .char_indices()
+            .nth(caret)
+            .map_or(text.len(), |(idx, _)| idx);
+        let start = text[..caret]
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| is_token_char(*c))
+            .last()
+            .map_or(caret, |(idx, _)| idx);
+        let end = text[caret..]
+            .char_indices()
+            .find(|(_, c)| !is_token_char(*c))
+            .map_or(text.len(), |(idx, _)| caret + idx);
+
+        Self {
+            range: start..end,
+            caret,
+        }
+    }
+
+    /// 光标前的部分，用于筛选候选项
+    fn query<'t>(&self, text: &'t str) -> &'t str {
+        &text[self.range.start..self.caret]
+    }
+
+    /// 用候选项替换单词，并把光标移动到候选项之后
+    fn replace(&self, ui: &Ui, text_id: Id, text: &mut String, candidate: &str) {
+        let caret = self.replace_text(text, candidate);
+        let mut state = TextEditState::load(ui.ctx(), text_id).unwrap_or_default();
+        state
+            .cursor
+            .set_char_range(Some(CCursorRange::one(CCursor::new(caret))));
+        state.store(ui.ctx(), text_id);
+    }
+
+    /// 用候选项替换单词，返回候选项之后的字符位置
+    fn replace_text(&self, text: &mut String, candidate: &str) -> usize {
+        text.replace_range(self.range.clone(), candidate);
+        text[..self.range.start].chars().count() + candidate.chars().count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_token_char(c: char) -> bool {
+        c.is_alphanumeric() || c == '_'
+    }
+
+    #[test]
+    fn token_at_caret() {
+        let text = "let foo_bar = baz;";
+        let token = Token::new(text, 7, is_token_char);
+        assert_eq!(token.range, 4..11);
+        assert_eq!(token.query(text), "foo");
+
+        // 光标在单词开头
+        let token = Token::new(text, 4, is_token_char);
+        assert_eq!(token.range, 4..11);
+        assert_eq!(token.query(text), "");
+
+        // 光标在文本末尾
+        let token = Token::new(text, 18, is_token_char);
+        assert_eq!(token.range, 18..18);
+        assert_eq!(token.query(text), "");
+
+        let token = Token::new(text, 17, is_token_char);
+        assert_eq!(token.range, 14..17);
+        assert_eq!(token.query(text), "baz");
+    }
+
+    #[test]
+    fn token_multi_byte() {
+        // 光标位置按字符计算，范围按字节计算
+        let text = "变量 名称_1 + x";
+        let token = Token::new(text, 5, is_token_char);
+        assert_eq!(token.range, 7..15);
+        assert_eq!(token.query(text), "名称");
+
+        let token = Token::new(text, 1, is_token_char);
+        assert_eq!(token.range, 0..6);
+        assert_eq!(token.query(text), "变");
+    }
+
+    #[test]
+    fn replace_token() {
+        let mut text = String::from("a.fo_x + 1");
+        let token = Token::new(&text, 4, is_token_char);
+        assert_eq!(token.replace_text(&mut text, "foobar"), 8);
+        assert_eq!(text, "a.foobar + 1");
+
+        let mut text = String::from("变量 名 + x");
+        let token = Token::new(&text, 4, is_token_char);
+        assert_eq!(token.replace_text(&mut text, "名称"), 5);
+        assert_eq!(text, "变量 名称 + x");
+
+        // 光标处没有单词时插入候选项
+        let mut text = String::from("a + ");
+        let token = Token::new(&text, 4, is_token_char);
+        assert_eq!(token.replace_text(&mut text, "b"), 5);
+        assert_eq!(text, "a + b");
+    }
+}
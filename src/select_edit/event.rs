@@ -0,0 +1,53 @@
+/// 选择框在一帧中发生的事件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectEvent {
+    /// 文本被输入修改
+    Typed,
+    /// 选中了候选项，参数为候选项在`iter`中的序号
+    Picked(usize),
+    /// 没有高亮的候选项时按回车确认了文本
+    Confirmed,
+    /// 选择了创建新值的选项
+    Created,
+    /// 严格模式下失去焦点时文本无效，自动选中了最匹配的候选项，参数为候选项在`iter`中的序号
+    Snapped(usize),
+    PopupOpened,
+    PopupClosed,
+}
+
+/// [`super::SelectEdit::show`]的返回值
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SelectOutput {
+    /// 按发生顺序排列的事件，弹出框打开和关闭的事件排在最后
+    pub events: Vec<SelectEvent>,
+    /// 被选中的候选项在`iter`中的序号，包括严格模式下自动选中的候选项
+    pub picked: Option<usize>,
+    /// 验证文本时返回的错误信息
+    pub error: Option<String>,
+}
+
+impl SelectOutput {
+    /// 文本是否通过了验证，没有设置验证时总是为`true`
+    pub fn is_valid(&self) -> bool {
+        self.error.is_none()
+    }
+
+    pub fn contains(&self, event: SelectEvent) -> bool {
+        self.events.contains(&event)
+    }
+
+    /// 文本是否被输入修改
+    pub fn typed(&self) -> bool {
+        self.contains(SelectEvent::Typed)
+    }
+
+    /// 是否按回车确认了文本
+    pub fn confirmed(&self) -> bool {
+        self.contains(SelectEvent::Confirmed)
+    }
+
+    /// 是否选择了创建新值的选项
+    pub fn created(&self) -> bool {
+        self.contains(SelectEvent::Created)
+    }
+}
@@ -0,0 +1,151 @@
+use egui::{Context, Id};
+use std::{collections::HashMap, hash::Hash};
+
+/// 依次为值、选择次数和最后一次选择的序号
+type Entries = Vec<(String, u32, u64)>;
+
+/// 已选择的值的历史记录，保存在egui的持久化内存中
+pub(crate) struct History {
+    id: Id,
+    max_len: usize,
+    entries: Entries,
+}
+
+impl History {
+    pub(crate) fn load(ctx: &Context, id: Id, max_len: usize) -> Self {
+        let entries = ctx.data_mut(|d| d.get_persisted::<Entries>(id).unwrap_or_default());
+        Self {
+            id,
+            max_len,
+            entries,
+        }
+    }
+
+    pub(crate) fn store(self, ctx: &Context) {
+        ctx.data_mut(|d| d.insert_persisted(self.id, self.entries));
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn last_seq(&self) -> u64 {
+        self.entries.iter().map(|e| e.2).max().unwrap_or_default()
+    }
+
+    /// 每个值综合选择次数和最近程度的排名，越高越靠前
+    pub(crate) fn ranks(&self) -> HashMap<&str, u64> {
+        let last_seq = self.last_seq();
+        self.entries
+            .iter()
+            .map(|(text, count, seq)| {
+                (
+                    text.as_str(),
+                    u64::from(*count) * 1024 / (1 + last_seq - seq),
+                )
+            })
+            .collect()
+    }
+
+    /// 最近选择的值，最近的在前
+    pub(crate) fn recent(&self, n: usize) -> Vec<&str> {
+        let mut entries = self.entries.iter().collect::<Vec<_>>();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.2));
+        entries.into_iter().take(n).map(|e| e.0.as_str()).collect()
+    }
+
+    pub(crate) fn record(&mut self, text: &str) {
+        let seq = self.last_seq() + 1;
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.0 == text) {
+            entry.1 += 1;
+            entry.2 = seq;
+        } else {
+            self.entries.push((text.to_string(), 1, seq));
+        }
+
+        if self.entries.len() > self.max_len {
+            self.entries.sort_by_key(|e| std::cmp::Reverse(e.2));
+            self.entries.truncate(self.max_len);
+        }
+    }
+}
+
+/// 清除使用[`super::SelectEdit::history`]保存的历史记录
+pub fn clear_history(ctx: &Context, id_salt: impl Hash) {
+    ctx.data_mut(|d| d.remove::<Entries>(Id::new(id_salt)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(max_len: usize) -> History {
+        History {
+            id: Id::new("history"),
+            max_len,
+            entries: vec![],
+        }
+    }
+
+    #[test]
+    fn record() {
+        let mut history = history(10);
+        assert!(history.is_empty());
+        history.record("a");
+        history.record("b");
+        history.record("a");
+        assert_eq!(
+            history.entries,
+            vec![("a".into(), 2, 3), ("b".into(), 1, 2)]
+        );
+        assert_eq!(history.recent(5), vec!["a", "b"]);
+        assert_eq!(history.recent(1), vec!["a"]);
+    }
+
+    #[test]
+    fn ranks() {
+        let mut history = history(10);
+        for text in ["a", "a", "a", "a", "b", "c"] {
+            history.record(text);
+        }
+        let ranks = history.ranks();
+        // 选择次数多的排在前面，次数相同时最近选择的排在前面
+        assert!(ranks["a"] > ranks["c"]);
+        assert!(ranks["c"] > ranks["b"]);
+        assert_eq!(ranks.get("d"), None);
+
+        // 很久以前选择的值排在最近选择的值之后
+        for _ in 0..5 {
+            history.record("b");
+        }
+        let ranks = history.ranks();
+        assert!(ranks["b"] > ranks["a"]);
+    }
+
+    #[test]
+    fn truncate() {
+        let mut history = history(2);
+        history.record("a");
+        history.record("a");
+        history.record("b");
+        history.record("c");
+        // 超出长度时删除最早选择的值，与选择次数无关
+        assert_eq!(history.recent(5), vec!["c", "b"]);
+        history.record("a");
+        assert_eq!(history.recent(5), vec!["a", "c"]);
+        assert_eq!(history.entries.len(), 2);
+    }
+
+    #[test]
+    fn store_and_clear() {
+        let ctx = Context::default();
+        let id = Id::new("history");
+        let mut history = History::load(&ctx, id, 10);
+        history.record("a");
+        history.store(&ctx);
+        assert_eq!(History::load(&ctx, id, 10).recent(5), vec!["a"]);
+
+        clear_history(&ctx, "history");
+        assert!(History::load(&ctx, id, 10).is_empty());
+    }
+}
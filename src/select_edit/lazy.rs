@@ -0,0 +1,257 @@
+use super::{accept_candidate, navigate, Matcher};
+use crate::row::{row_height, scroll_to_row};
+use egui::{Popup, PopupCloseBehavior, Response, RichText, ScrollArea, TextEdit, Ui, Widget};
+use std::ops::Range;
+
+/// 按需提供候选项，用于候选项数量很多的情况
+pub trait CandidateProvider {
+    /// 返回匹配`query`的候选项中序号在`range`范围内的部分，以及匹配的候选项总数
+    fn query(&mut self, query: &str, range: Range<usize>) -> Page;
+}
+
+/// 一页候选项
+#[derive(Debug, Clone, Default)]
+pub struct Page {
+    pub items: Vec<String>,
+    /// 匹配的候选项总数
+    pub total: usize,
+}
+
+/// 从列表中筛选候选项，并缓存上一次查询的结果
+#[derive(Debug)]
+pub struct ListProvider {
+    pub items: Vec<String>,
+    pub matcher: Matcher<'static>,
+    cache: Option<(String, Vec<usize>)>,
+}
+
+impl ListProvider {
+    pub fn new(items: Vec<String>) -> Self {
+        Self {
+            items,
+            matcher: Matcher::default(),
+            cache: None,
+        }
+    }
+
+    pub fn matcher(self, matcher: Matcher<'static>) -> Self {
+        Self {
+            matcher,
+            cache: None,
+            ..self
+        }
+    }
+
+    /// `items`被修改后需要调用以清除缓存的查询结果
+    pub fn clear_cache(&mut self) {
+        self.cache = None;
+    }
+}
+
+impl CandidateProvider for ListProvider {
+    fn query(&mut self, query: &str, range: Range<usize>) -> Page {
+        if query.is_empty() {
+            let range = range.start.min(self.items.len())..range.end.min(self.items.len());
+            return Page {
+                items: self.items[range].to_vec(),
+                total: self.items.len(),
+            };
+        }
+
+        if self.cache.as_ref().is_none_or(|(q, _)| q != query) {
+            let mut matched = self
+                .items
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, item)| self.matcher.matches(query, item).map(|m| (m.score, idx)))
+                .collect::<Vec<_>>();
+            matched.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+            self.cache = Some((
+                query.to_string(),
+                matched.into_iter().map(|(_, idx)| idx).collect(),
+            ));
+        }
+
+        let indices = self
+            .cache
+            .as_ref()
+            .map(|(_, i)| i.as_slice())
+            .unwrap_or(&[]);
+        Page {
+            items: indices
+                .iter()
+                .skip(range.start)
+                .take(range.len())
+                .map(|idx| self.items[*idx].clone())
+                .collect(),
+            total: indices.len(),
+        }
+    }
+}
+
+/// 从[`CandidateProvider`]获取候选项的选择框。
+///
+/// 弹出框中只获取和显示可见的候选项
+pub struct LazySelectEdit<'a, P: CandidateProvider> {
+    pub text: &'a mut String,
+    pub provider: &'a mut P,
+    /// 最多显示的候选项数量
+    pub max_results: usize,
+    pub hint_text: Option<&'a str>,
+    /// 超出`max_results`的候选项数量的显示文字
+    pub more_results_text: fn(usize) -> String,
+}
+
+impl<'a, P: CandidateProvider> LazySelectEdit<'a, P> {
+    pub fn new(text: &'a mut String, provider: &'a mut P) -> Self {
+        Self {
+            text,
+            provider,
+            max_results: 1000,
+            hint_text: None,
+            more_results_text: |n| format!("{n} more results"),
+        }
+    }
+
+    pub fn max_results(self, max_results: usize) -> Self {
+        Self {
+            max_results,
+            ..self
+        }
+    }
+
+    pub fn hint_text(self, hint_text: impl Into<Option<&'a str>>) -> Self {
+        Self {
+            hint_text: hint_text.into(),
+            ..self
+        }
+    }
+
+    pub fn more_results_text(self, more_results_text: fn(usize) -> String) -> Self {
+        Self {
+            more_results_text,
+            ..self
+        }
+    }
+}
+
+impl<'a, P: CandidateProvider> Widget for LazySelectEdit<'a, P> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let LazySelectEdit {
+            text,
+            provider,
+            max_results,
+            hint_text,
+            more_results_text,
+        } = self;
+
+        let text_id = ui.next_auto_id();
+        let popup_id = ui.auto_id_with(module_path!()).with("select editor popup");
+        let highlight_id = popup_id.with("highlight");
+        let scroll_id = popup_id.with("scroll");
+
+        // 用编辑前的文本处理选择候选项的按键
+        let rows = provider.query(text, 0..0).total.min(max_results);
+
+        let mut highlight = ui
+            .data(|d| d.get_temp::<Option<usize>>(highlight_id))
+            .flatten()
+            .filter(|h| *h < rows);
+        let mut scroll_to_highlight = false;
+        let mut changed = false;
+
+        if let Some(h) = navigate(
+            ui,
+            text_id,
+            popup_id,
+            rows,
+            &mut highlight,
+            &mut scroll_to_highlight,
+        ) {
+            if let Some(item) = provider.query(text, h..h + 1).items.pop() {
+                accept_candidate(ui, text_id, text, &item);
+                changed = true;
+            }
+            Popup::close_id(ui.ctx(), popup_id);
+            highlight = None;
+        }
+
+        let mut text_edit = TextEdit::singleline(text).id(text_id);
+        if let Some(hint_text) = hint_text {
+            text_edit = text_edit.hint_text(hint_text);
+        }
+
+        let mut resp = ui.add(text_edit);
+        if resp.changed() {
+            highlight = None;
+            changed = true;
+        }
+
+        let total = provider.query(text, 0..0).total;
+        let rows = total.min(max_results);
+        let highlight = highlight.filter(|h| *h < rows);
+        let row_height = row_height(ui);
+
+        Popup::menu(&resp)
+            .id(popup_id)
+            .close_behavior(PopupCloseBehavior::IgnoreClicks)
+            .show(|ui| {
+                ui.set_width(
+                    resp.rect.width()
+                        - ui.style().spacing.menu_margin.leftf()
+                        - ui.style().spacing.menu_margin.rightf(),
+                );
+
+                let mut scroll_area = ScrollArea::vertical().max_height(100.0);
+
+                if let (Some(h), true) = (highlight, scroll_to_highlight) {
+                    let viewport = ui.data(|d| d.get_temp(scroll_id)).unwrap_or_default();
+                    scroll_area = scroll_to_row(ui, scroll_area, h, row_height, viewport);
+                }
+
+                let output = scroll_area.show_rows(ui, row_height, rows, |ui, range| {
+                    let start = range.start;
+                    let page = provider.query(text, range);
+                    for (idx, item) in page.items.into_iter().enumerate() {
+                        let idx = start + idx;
+                        let mut r = ui.selectable_label(*text == item, &item);
+                        if highlight == Some(idx) {
+                            r = r.highlight();
+                        }
+                        if r.clicked() {
+                            *text = item;
+                            changed = true;
+                        }
+                    }
+                });
+                ui.data_mut(|d| {
+                    d.insert_temp(
+                        scroll_id,
+                        (output.state.offset.y, output.inner_rect.height()),
+                    )
+                });
+
+                if total > rows {
+                    ui.label(RichText::new(more_results_text(total - rows)).weak());
+                }
+            });
+
+        if (resp.lost_focus() || ui.input(|i| i.key_pressed(egui::Key::Escape)))
+            && Popup::is_id_open(ui.ctx(), popup_id)
+        {
+            Popup::close_id(ui.ctx(), popup_id);
+        }
+
+        if Popup::is_id_open(ui.ctx(), popup_id) {
+            ui.data_mut(|d| d.insert_temp(highlight_id, highlight));
+        } else {
+            ui.data_mut(|d| d.remove::<Option<usize>>(highlight_id));
+        }
+
+        if changed {
+            resp.mark_changed();
+        }
+
+        resp
+    }
+}
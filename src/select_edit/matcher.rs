@@ -0,0 +1,232 @@
+use std::ops::Range;
+
+/// 候选项的匹配方式
+#[derive(Default)]
+pub enum Matcher<'a> {
+    /// 包含搜索文本（区分大小写）
+    #[default]
+    Contains,
+    /// 以搜索文本开头（区分大小写）
+    Prefix,
+    /// 包含搜索文本（不区分大小写）
+    IgnoreCase,
+    /// 某个单词以搜索文本开头（不区分大小写）。
+    ///
+    /// 单词的起始位置包括文本开头、非字母数字字符之后以及小写字母到大写字母的转换处
+    WordBoundary,
+    /// 搜索文本的字符按顺序出现在候选项中（不区分大小写），并根据连续程度和单词起始位置评分
+    Fuzzy,
+    /// 自定义匹配方法。参数依次为搜索文本和候选项，返回`None`表示不匹配
+    Custom(MatchFn<'a>),
+}
+
+pub type MatchFn<'a> = Box<dyn Fn(&str, &str) -> Option<MatchResult> + 'a>;
+
+/// 匹配结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MatchResult {
+    /// 匹配得分，越高越靠前。为`None`时保持候选项原有的顺序
+    pub score: Option<i32>,
+    /// 候选项中被匹配部分的字节范围，用于高亮显示
+    pub ranges: Vec<Range<usize>>,
+}
+
+impl<'a> Matcher<'a> {
+    pub fn custom(f: impl Fn(&str, &str) -> Option<MatchResult> + 'a) -> Self {
+        Self::Custom(Box::new(f))
+    }
+
+    /// 使用搜索文本`query`匹配候选项`text`
+    pub fn matches(&self, query: &str, text: &str) -> Option<MatchResult> {
+        let unscored = |range: Option<Range<usize>>| {
+            range.map(|range| MatchResult {
+                score: None,
+                ranges: vec![range],
+            })
+        };
+
+        match self {
+            Matcher::Contains => unscored(text.find(query).map(|s| s..s + query.len())),
+            Matcher::Prefix => unscored(text.starts_with(query).then_some(0..query.len())),
+            Matcher::IgnoreCase => unscored(find_ignore_case(text, query, |_| true)),
+            Matcher::WordBoundary => unscored(find_ignore_case(text, query, |idx| {
+                is_word_start(text, idx)
+            })),
+            Matcher::Fuzzy => fuzzy(query, text).map(|(score, ranges)| MatchResult {
+                score: Some(score),
+                ranges,
+            }),
+            Matcher::Custom(f) => f(query, text),
+        }
+    }
+}
+
+impl std::fmt::Debug for Matcher<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Contains => write!(f, "Contains"),
+            Self::Prefix => write!(f, "Prefix"),
+            Self::IgnoreCase => write!(f, "IgnoreCase"),
+            Self::WordBoundary => write!(f, "WordBoundary"),
+            Self::Fuzzy => write!(f, "Fuzzy"),
+            Self::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+fn eq_ignore_case(a: char, b: char) -> bool {
+    a == b || a.to_lowercase().eq(b.to_lowercase())
+}
+
+/// 查找第一个满足`accept`且不区分大小写匹配`query`的位置，返回匹配的字节范围
+fn find_ignore_case(
+    text: &str,
+    query: &str,
+    accept: impl Fn(usize) -> bool,
+) -> Option<Range<usize>> {
+    // 与`str::find`一致，空的查询匹配任何文本的开头
+    if query.is_empty() {
+        return Some(0..0);
+    }
+    text.char_indices()
+        .filter(|(idx, _)| accept(*idx))
+        .find_map(|(start, _)| {
+            let mut rest = text[start..].char_indices();
+            let mut end = start;
+            for q in query.chars() {
+                let (offset, c) = rest.next()?;
+                if !eq_ignore_case(c, q) {
+                    return None;
+                }
+                end = start + offset + c.len_utf8();
+            }
+            Some(start..end)
+        })
+}
+
+/// 字节位置`idx`处的字符是否为单词的起始字符
+fn is_word_start(text: &str, idx: usize) -> bool {
+    let Some(current) = text[idx..].chars().next() else {
+        return false;
+    };
+
+    match text[..idx].chars().next_back() {
+        None => true,
+        Some(prev) => {
+            (!prev.is_alphanumeric() && current.is_alphanumeric())
+                || (prev.is_lowercase() && current.is_uppercase())
+        }
+    }
+}
+
+/// 子序列模糊匹配，返回匹配得分和匹配的字节范围
+fn fuzzy(query: &str, text: &str) -> Option<(i32, Vec<Range<usize>>)> {
+    const MATCH: i32 = 16;
+    const CONSECUTIVE: i32 = 16;
+    const WORD_START: i32 = 24;
+    const GAP: i32 = 1;
+
+    let mut score = 0;
+    let mut chars = text.char_indices().enumerate();
+    let mut last_pos: Option<usize> = None;
+    let mut ranges: Vec<Range<usize>> = vec![];
+
+    for q in query.chars() {
+        let (pos, (idx, c)) = chars.by_ref().find(|(_, (_, c))| eq_ignore_case(*c, q))?;
+        let end = idx + c.len_utf8();
+        match ranges.last_mut() {
+            Some(last) if last.end == idx => last.end = end,
+            _ => ranges.push(idx..end),
+        }
+
+        score += MATCH;
+        if is_word_start(text, idx) {
+            score += WORD_START;
+        }
+        match last_pos {
+            Some(last) if last + 1 == pos => score += CONSECUTIVE,
+            Some(last) => score -= GAP * (pos - last - 1) as i32,
+            None => score -= GAP * pos as i32,
+        }
+        last_pos = Some(pos);
+    }
+
+    Some((score, ranges))
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)]
+mod tests {
+    use super::*;
+
+    fn ranges(matcher: Matcher, query: &str, text: &str) -> Option<Vec<Range<usize>>> {
+        matcher.matches(query, text).map(|m| m.ranges)
+    }
+
+    fn score(query: &str, text: &str) -> i32 {
+        fuzzy(query, text).unwrap().0
+    }
+
+    #[test]
+    fn ignore_case_multi_byte() {
+        assert_eq!(
+            ranges(Matcher::IgnoreCase, "ä", "Straße Ä"),
+            Some(vec![8..10])
+        );
+        assert_eq!(ranges(Matcher::IgnoreCase, "SSE", "Straße"), None);
+        // 开尔文符号（3字节）的小写为k，范围按候选项中的字节计算
+        assert_eq!(
+            ranges(Matcher::IgnoreCase, "kel", "\u{212A}elvin"),
+            Some(vec![0..5])
+        );
+        assert_eq!(find_ignore_case("abc", "abcd", |_| true), None);
+    }
+
+    #[test]
+    fn word_boundary() {
+        assert!(is_word_start("fooBar", 0));
+        assert!(is_word_start("fooBar", 3));
+        assert!(is_word_start("foo_bar", 4));
+        assert!(!is_word_start("foobar", 3));
+        assert!(!is_word_start("foo", 3));
+        assert!(is_word_start("é-ü", 3));
+
+        assert_eq!(
+            ranges(Matcher::WordBoundary, "ba", "fooBar baz"),
+            Some(vec![3..5])
+        );
+        assert_eq!(
+            ranges(Matcher::WordBoundary, "ba", "foobar baz"),
+            Some(vec![7..9])
+        );
+        assert_eq!(ranges(Matcher::WordBoundary, "oo", "foobar"), None);
+    }
+
+    #[test]
+    fn fuzzy_ranges() {
+        assert_eq!(fuzzy("ab", "xaby").unwrap().1, vec![1..3]);
+        assert_eq!(fuzzy("ac", "abc").unwrap().1, vec![0..1, 2..3]);
+        assert_eq!(fuzzy("éa", "Éta").unwrap().1, vec![0..2, 3..4]);
+        assert_eq!(fuzzy("ba", "ab"), None);
+    }
+
+    #[test]
+    fn fuzzy_score_order() {
+        // 连续匹配优先
+        assert!(score("abc", "abcx") > score("abc", "axbxc"));
+        // 单词起始位置优先
+        assert!(score("fb", "foo_bar") > score("fb", "xfoob"));
+        assert!(score("fb", "fooBar") > score("fb", "foobar"));
+        // 越靠前越好
+        assert!(score("a", "abc") > score("a", "xxa"));
+    }
+
+    #[test]
+    fn empty_query() {
+        assert_eq!(fuzzy("", "abc"), Some((0, vec![])));
+        assert_eq!(ranges(Matcher::Contains, "", "abc"), Some(vec![0..0]));
+        assert_eq!(ranges(Matcher::Prefix, "", "abc"), Some(vec![0..0]));
+        assert_eq!(ranges(Matcher::IgnoreCase, "", "abc"), Some(vec![0..0]));
+        assert_eq!(ranges(Matcher::IgnoreCase, "", ""), Some(vec![0..0]));
+    }
+}
@@ -0,0 +1,734 @@
+use crate::{
+    highlight::highlight_text,
+    row::{row_height, selectable_row},
+};
+use egui::{
+    text::{CCursor, CCursorRange},
+    text_edit::TextEditState,
+    Align, Id, InnerResponse, Key, Layout, Modifiers, Popup, PopupCloseBehavior, RectAlign,
+    RichText, ScrollArea, SetOpenCommand, Stroke, StrokeKind, TextEdit, UiBuilder, Widget,
+};
+use history::History;
+use std::ops::Range;
+
+pub use async_source::{AsyncCandidates, AsyncSelectEdit, QueryRequest};
+pub use completion::CompletionEdit;
+pub use event::{SelectEvent, SelectOutput};
+pub use history::clear_history;
+pub use lazy::{CandidateProvider, LazySelectEdit, ListProvider, Page};
+pub use matcher::{MatchFn, MatchResult, Matcher};
+pub use options::{OpenOn, Placement, PopupHeight};
+pub use row::{CandidateRow, RowUiFn};
+pub use tags::TagEdit;
+pub use value::SelectValue;
+
+mod async_source;
+mod completion;
+mod event;
+mod history;
+mod lazy;
+mod matcher;
+mod options;
+mod row;
+mod tags;
+mod value;
+
+pub type CreateFn<'a> = Box<dyn FnMut(&str) + 'a>;
+pub type ValidateFn<'a> = Box<dyn Fn(&str) -> Result<(), String> + 'a>;
+
+pub struct SelectEdit<'a, S, L>
+where
+    S: ToString,
+    L: Iterator<Item = S>,
+{
+    pub text: &'a mut String,
+    pub iter: L,
+    pub filter: bool,
+    pub matcher: Matcher<'a>,
+    pub hint_text: Option<&'a str>,
+    pub strict: bool,
+    /// 候选项的分组。依次为分组名称和该组候选项的数量，按顺序对应`iter`中的候选项
+    pub groups: Vec<(String, usize)>,
+    /// 自定义候选项的显示方式
+    pub row_ui: Option<RowUiFn<'a>>,
+    /// 候选项是否正在加载。为`true`时弹出框中显示加载动画
+    pub loading: bool,
+    /// 历史记录的id和最大数量
+    pub history: Option<(Id, usize)>,
+    /// 文本为空时显示的最近选择的值的标题
+    pub recent_text: &'a str,
+    /// 点击创建新值时调用，参数为文本框中的文字
+    pub on_create: Option<CreateFn<'a>>,
+    /// 创建新值的选项的显示文字
+    pub create_text: fn(&str) -> String,
+    pub popup_height: PopupHeight,
+    /// 弹出框的最小宽度。默认与文本框宽度一致
+    pub popup_min_width: Option<f32>,
+    /// 弹出框的最大宽度。默认与文本框宽度一致
+    pub popup_max_width: Option<f32>,
+    pub placement: Placement,
+    pub open_on: OpenOn,
+    /// 点击候选项后文本框是否保持焦点
+    pub keep_focus: bool,
+    /// 检查文本是否有效，无效时返回错误信息
+    pub validator: Option<ValidateFn<'a>>,
+    /// 是否在文本框下方显示错误信息。默认只在鼠标悬停时显示
+    pub inline_error: bool,
+    /// 文本框和弹出框id的来源。默认使用自动生成的id
+    pub id_salt: Option<Id>,
+}
+
+impl<'a, S, L> SelectEdit<'a, S, L>
+where
+    S: ToString,
+    L: Iterator<Item = S>,
+{
+    pub fn new(text: &'a mut String, iter: L) -> Self {
+        Self {
+            text,
+            iter,
+            filter: false,
+            matcher: Matcher::default(),
+            hint_text: None,
+            strict: false,
+            groups: vec![],
+            row_ui: None,
+            loading: false,
+            history: None,
+            recent_text: "Recent",
+            on_create: None,
+            create_text: |text| format!("Create \"{text}\""),
+            popup_height: PopupHeight::default(),
+            popup_min_width: None,
+            popup_max_width: None,
+            placement: Placement::default(),
+            open_on: OpenOn::default(),
+            keep_focus: false,
+            validator: None,
+            inline_error: false,
+            id_salt: None,
+        }
+    }
+
+    pub fn filter(self) -> Self {
+        Self {
+            filter: true,
+            ..self
+        }
+    }
+
+    /// 使用指定的匹配方式筛选候选项，同时启用筛选。
+    ///
+    /// 匹配结果带有得分时，候选项按得分从高到低排列
+    pub fn matcher(self, matcher: Matcher<'a>) -> Self {
+        Self {
+            filter: true,
+            matcher,
+            ..self
+        }
+    }
+
+    pub fn hint_text(self, hint_text: impl Into<Option<&'a str>>) -> Self {
+        Self {
+            hint_text: hint_text.into(),
+            ..self
+        }
+    }
+
+    /// 自定义弹出框中候选项的显示方式，例如添加图标、说明文字或右对齐的快捷键。
+    ///
+    /// 选中后写入文本框的仍然是候选项的文字
+    pub fn row_ui(self, row_ui: impl Fn(&mut egui::Ui, &CandidateRow) + 'a) -> Self {
+        Self {
+            row_ui: Some(Box::new(row_ui)),
+            ..self
+        }
+    }
+
+    pub fn loading(self, loading: bool) -> Self {
+        Self { loading, ..self }
+    }
+
+    /// 记录选择过的值，保存在egui的持久化内存中，最多保存`max_len`个。
+    ///
+    /// 弹出框中经常和最近选择的值排在前面，文本为空时在最前面显示最近选择的值。
+    /// 可以使用[`clear_history`]清除历史记录
+    pub fn history(self, id_salt: impl std::hash::Hash, max_len: usize) -> Self {
+        Self {
+            history: Some((Id::new(id_salt), max_len)),
+            ..self
+        }
+    }
+
+    pub fn recent_text(self, recent_text: &'a str) -> Self {
+        Self {
+            recent_text,
+            ..self
+        }
+    }
+
+    /// 文本不是任一候选项时，在弹出框最后显示创建新值的选项，选择该选项时调用`on_create`
+    pub fn on_create(self, on_create: impl FnMut(&str) + 'a) -> Self {
+        Self {
+            on_create: Some(Box::new(on_create)),
+            ..self
+        }
+    }
+
+    pub fn create_text(self, create_text: fn(&str) -> String) -> Self {
+        Self {
+            create_text,
+            ..self
+        }
+    }
+
+    /// 弹出框的最大高度。默认为100
+    pub fn popup_height(self, popup_height: PopupHeight) -> Self {
+        Self {
+            popup_height,
+            ..self
+        }
+    }
+
+    /// 弹出框的宽度范围。弹出框的宽度为限制在该范围内的文本框宽度
+    pub fn popup_width(self, min: impl Into<Option<f32>>, max: impl Into<Option<f32>>) -> Self {
+        Self {
+            popup_min_width: min.into(),
+            popup_max_width: max.into(),
+            ..self
+        }
+    }
+
+    pub fn placement(self, placement: Placement) -> Self {
+        Self { placement, ..self }
+    }
+
+    pub fn open_on(self, open_on: OpenOn) -> Self {
+        Self { open_on, ..self }
+    }
+
+    /// 点击候选项后文本框保持焦点，便于连续输入
+    pub fn keep_focus(self) -> Self {
+        Self {
+            keep_focus: true,
+            ..self
+        }
+    }
+
+    /// 检查文本是否有效。
+    ///
+    /// 文本无效时文本框显示为错误颜色，并显示返回的错误信息。错误信息可以从[`SelectOutput::error`]获取
+    pub fn validator(self, validator: impl Fn(&str) -> Result<(), String> + 'a) -> Self {
+        Self {
+            validator: Some(Box::new(validator)),
+            ..self
+        }
+    }
+
+    /// 在文本框下方显示错误信息
+    pub fn inline_error(self) -> Self {
+        Self {
+            inline_error: true,
+            ..self
+        }
+    }
+
+    /// 使用固定的id。之前的控件数量会变化时使用，避免文本框失去焦点
+    pub fn id_salt(self, id_salt: impl std::hash::Hash) -> Self {
+        Self {
+            id_salt: Some(Id::new(id_salt)),
+            ..self
+        }
+    }
+
+    /// 只允许选择候选项中的值。
+    ///
+    /// 文本框失去焦点（包括按下回车）时，若文本不是任一候选项，则替换为最匹配的候选项；
+    /// 没有匹配的候选项时恢复为上一个有效值。文本无效时文本框显示为错误颜色。空文本视为有效
+    pub fn strict(self) -> Self {
+        Self {
+            strict: true,
+            ..self
+        }
+    }
+}
+
+impl<'a, S> SelectEdit<'a, S, std::vec::IntoIter<S>>
+where
+    S: ToString,
+{
+    /// 使用分组的候选项创建。
+    ///
+    /// 弹出框中每组候选项前显示不可选择的分组名称，筛选时保持分组的顺序，并隐藏没有候选项的分组
+    pub fn grouped<G, I>(text: &'a mut String, groups: impl IntoIterator<Item = (G, I)>) -> Self
+    where
+        G: ToString,
+        I: IntoIterator<Item = S>,
+    {
+        let mut items = vec![];
+        let groups = groups
+            .into_iter()
+            .map(|(group, iter)| {
+                let len = items.len();
+                items.extend(iter);
+                (group.to_string(), items.len() - len)
+            })
+            .collect();
+
+        Self {
+            groups,
+            ..Self::new(text, items.into_iter())
+        }
+    }
+}
+
+impl<'a, S, L> Widget for SelectEdit<'a, S, L>
+where
+    S: ToString,
+    L: Iterator<Item = S>,
+{
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        self.show(ui).response
+    }
+}
+
+impl<'a, S, L> SelectEdit<'a, S, L>
+where
+    S: ToString,
+    L: Iterator<Item = S>,
+{
+    /// 显示选择框，同时返回本帧发生的事件
+    pub fn show(mut self, ui: &mut egui::Ui) -> InnerResponse<SelectOutput> {
+        let (text_id, popup_id) = match self.id_salt {
+            Some(id_salt) => {
+                let id = ui.make_persistent_id(id_salt);
+                (id, id.with("select editor popup"))
+            }
+            None => (
+                ui.next_auto_id(),
+                ui.auto_id_with(module_path!()).with("select editor popup"),
+            ),
+        };
+        let highlight_id = popup_id.with("highlight");
+        let last_valid_id = text_id.with("last valid");
+
+        let items = self.iter.map(|item| item.to_string()).collect::<Vec<_>>();
+        let is_valid = |text: &String| text.is_empty() || items.contains(text);
+        if self.strict && is_valid(self.text) {
+            ui.data_mut(|d| d.insert_temp(last_valid_id, self.text.clone()));
+        }
+
+        let mut candidates = if self.text.is_empty() || !self.filter {
+            items
+                .iter()
+                .enumerate()
+                .map(|(index, text)| Candidate {
+                    index,
+                    text: text.clone(),
+                    ranges: vec![],
+                    recent: false,
+                })
+                .collect()
+        } else {
+            match_candidates(&self.matcher, self.text, &items)
+        };
+
+        let history = self
+            .history
+            .map(|(id, max_len)| History::load(ui.ctx(), id, max_len));
+        if let Some(history) = &history {
+            let ranks = history.ranks();
+            candidates.sort_by_key(|c| std::cmp::Reverse(ranks.get(c.text.as_str()).copied()));
+        }
+        if !self.groups.is_empty() {
+            candidates.sort_by_key(|c| group_of(&self.groups, c.index));
+        }
+        if let Some(history) = history
+            .as_ref()
+            .filter(|h| self.text.is_empty() && !h.is_empty())
+        {
+            let recent = history
+                .recent(RECENT_COUNT)
+                .into_iter()
+                .filter_map(|text| {
+                    items
+                        .iter()
+                        .position(|item| item == text)
+                        .map(|index| Candidate {
+                            index,
+                            text: text.to_string(),
+                            ranges: vec![],
+                            recent: true,
+                        })
+                })
+                .collect::<Vec<_>>();
+            candidates.splice(0..0, recent);
+        }
+
+        // 创建新值的选项位于所有候选项之后
+        let mut on_create = self.on_create.take();
+        let can_create = on_create.is_some() && !is_valid(self.text);
+        let create_index = candidates.len();
+        let rows = candidates.len() + usize::from(can_create);
+
+        let mut highlight = ui
+            .data(|d| d.get_temp::<Option<usize>>(highlight_id))
+            .flatten()
+            .filter(|h| *h < rows);
+        let mut scroll_to_highlight = false;
+        let mut changed = false;
+        let mut picked = None;
+        let mut events = vec![];
+        let was_open = Popup::is_id_open(ui.ctx(), popup_id);
+
+        let mut create = false;
+        if let Some(h) = navigate(
+            ui,
+            text_id,
+            popup_id,
+            rows,
+            &mut highlight,
+            &mut scroll_to_highlight,
+        ) {
+            if h == create_index {
+                create = true;
+            } else {
+                accept_candidate(ui, text_id, self.text, &candidates[h].text);
+                changed = true;
+                picked = Some(candidates[h].index);
+                events.push(SelectEvent::Picked(candidates[h].index));
+            }
+            Popup::close_id(ui.ctx(), popup_id);
+            highlight = None;
+        }
+
+        let hint_text = self.hint_text;
+        let add_text_edit = |ui: &mut egui::Ui, text: &mut String| {
+            let mut text_edit = TextEdit::singleline(text).id(text_id);
+            if let Some(hint_text) = hint_text {
+                text_edit = text_edit.hint_text(hint_text);
+            }
+            ui.add(text_edit)
+        };
+
+        // 错误信息在文本框下方显示，但要等候选项、严格模式和创建修改文本之后才能验证
+        let mut inline_error_ui = (self.validator.is_some() && self.inline_error)
+            .then(|| ui.new_child(UiBuilder::new().layout(Layout::top_down(Align::Min))));
+        let mut resp = add_text_edit(inline_error_ui.as_mut().unwrap_or(ui), self.text);
+        if resp.changed() {
+            highlight = None;
+            changed = true;
+            events.push(SelectEvent::Typed);
+        }
+        if resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)) {
+            events.push(SelectEvent::Confirmed);
+        }
+
+        let open_command = match self.open_on {
+            OpenOn::Click => resp.clicked().then_some(SetOpenCommand::Toggle),
+            OpenOn::Focus => resp.gained_focus().then_some(SetOpenCommand::Bool(true)),
+            OpenOn::Typing => resp.changed().then_some(SetOpenCommand::Bool(true)),
+        };
+        let (align, alternative) = match self.placement {
+            Placement::Below => (RectAlign::BOTTOM_START, &[RectAlign::TOP_START]),
+            Placement::Above => (RectAlign::TOP_START, &[RectAlign::BOTTOM_START]),
+        };
+        let popup_width = resp
+            .rect
+            .width()
+            .max(self.popup_min_width.unwrap_or(0.0))
+            .min(self.popup_max_width.unwrap_or(f32::INFINITY));
+        let max_height = match self.popup_height {
+            PopupHeight::Points(height) => height,
+            PopupHeight::Rows(rows) => {
+                rows as f32 * (row_height(ui) + ui.spacing().item_spacing.y)
+                    - ui.spacing().item_spacing.y
+            }
+        };
+        let mut clicked = false;
+
+        egui::Popup::menu(&resp)
+            .id(popup_id)
+            .open_memory(open_command)
+            .align(align)
+            .align_alternatives(alternative)
+            .close_behavior(PopupCloseBehavior::IgnoreClicks)
+            .show(|ui| {
+                ui.set_width(
+                    popup_width
+                        - ui.style().spacing.menu_margin.leftf()
+                        - ui.style().spacing.menu_margin.rightf(),
+                );
+                ScrollArea::vertical()
+                    .max_height(max_height)
+                    .show(ui, |ui| {
+                        let mut last_section = None;
+                        for (idx, c) in candidates.iter().enumerate() {
+                            let group = group_of(&self.groups, c.index).filter(|_| !c.recent);
+                            let section = Some((c.recent, group));
+                            if section != last_section {
+                                let title = if c.recent {
+                                    Some(self.recent_text)
+                                } else {
+                                    group.map(|g| self.groups[g].0.as_str())
+                                };
+                                if let Some(title) = title {
+                                    ui.label(RichText::new(title).small().weak());
+                                }
+                                last_section = section;
+                            }
+
+                            let selected = *self.text == c.text;
+                            let mut r = if let Some(row_ui) = &self.row_ui {
+                                let row = CandidateRow {
+                                    index: c.index,
+                                    text: &c.text,
+                                    ranges: &c.ranges,
+                                    selected,
+                                };
+                                selectable_row(ui, selected, |ui| {
+                                    ui.set_min_width(ui.available_width());
+                                    row_ui(ui, &row)
+                                })
+                            } else {
+                                let label = highlight_text(ui, &c.text, &c.ranges);
+                                ui.selectable_label(selected, label)
+                            };
+                            if highlight == Some(idx) {
+                                r = r.highlight();
+                                if scroll_to_highlight {
+                                    r.scroll_to_me(None);
+                                }
+                            }
+                            if r.clicked() {
+                                c.text.clone_into(self.text);
+                                changed = true;
+                                clicked = true;
+                                picked = Some(c.index);
+                                events.push(SelectEvent::Picked(c.index));
+                            }
+                        }
+
+                        if can_create {
+                            let mut r = ui.selectable_label(false, (self.create_text)(self.text));
+                            if highlight == Some(create_index) {
+                                r = r.highlight();
+                                if scroll_to_highlight {
+                                    r.scroll_to_me(None);
+                                }
+                            }
+                            if r.clicked() {
+                                create = true;
+                                clicked = true;
+                            }
+                        }
+                    });
+
+                if self.loading {
+                    ui.spinner();
+                }
+            });
+
+        if clicked && self.keep_focus {
+            move_cursor_to_end(ui, text_id, self.text);
+            resp.request_focus();
+        }
+
+        if create {
+            if let Some(on_create) = &mut on_create {
+                on_create(self.text);
+                changed = true;
+                events.push(SelectEvent::Created);
+            }
+        }
+
+        if self.strict && resp.lost_focus() && !is_valid(self.text) && !create {
+            let best = match_candidates(&self.matcher, self.text, &items)
+                .into_iter()
+                .next();
+            if let Some(best) = best {
+                picked = Some(best.index);
+                events.push(SelectEvent::Snapped(best.index));
+                *self.text = best.text;
+            } else {
+                *self.text = ui.data(|d| d.get_temp(last_valid_id)).unwrap_or_default();
+            }
+            changed = true;
+        }
+
+        let error = self.validator.as_ref().and_then(|v| v(self.text).err());
+        if self.strict && !is_valid(self.text) || error.is_some() {
+            paint_invalid(ui, resp.rect);
+        }
+        if let Some(mut error_ui) = inline_error_ui {
+            if let Some(error) = &error {
+                error_ui.label(
+                    RichText::new(error)
+                        .small()
+                        .color(error_ui.visuals().error_fg_color),
+                );
+            }
+            ui.advance_cursor_after_rect(error_ui.min_rect());
+        } else if let Some(error) = &error {
+            resp = resp.on_hover_text(error);
+        }
+
+        if let (Some(mut history), Some(idx)) = (history, picked) {
+            history.record(&items[idx]);
+            history.store(ui.ctx());
+        }
+
+        if (resp.lost_focus() || ui.input(|i| i.key_pressed(Key::Escape)))
+            && Popup::is_id_open(ui.ctx(), popup_id)
+        {
+            Popup::close_id(ui.ctx(), popup_id);
+        }
+
+        let is_open = Popup::is_id_open(ui.ctx(), popup_id);
+        if is_open {
+            ui.data_mut(|d| d.insert_temp(highlight_id, highlight));
+        } else {
+            ui.data_mut(|d| d.remove::<Option<usize>>(highlight_id));
+        }
+
+        if is_open != was_open {
+            events.push(if is_open {
+                SelectEvent::PopupOpened
+            } else {
+                SelectEvent::PopupClosed
+            });
+        }
+
+        if changed {
+            resp.mark_changed();
+        }
+
+        InnerResponse::new(
+            SelectOutput {
+                events,
+                picked,
+                error,
+            },
+            resp,
+        )
+    }
+}
+
+/// 使用`matcher`筛选`items`，并按得分排序
+fn match_candidates(matcher: &Matcher, query: &str, items: &[String]) -> Vec<Candidate> {
+    let mut matched = items
+        .iter()
+        .enumerate()
+        .filter_map(|(index, text)| {
+            matcher.matches(query, text).map(|m| {
+                (
+                    m.score,
+                    Candidate {
+                        index,
+                        text: text.clone(),
+                        ranges: m.ranges,
+                        recent: false,
+                    },
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+    matched.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    matched.into_iter().map(|(_, c)| c).collect()
+}
+
+/// 文本为空时显示的最近选择的值的数量
+const RECENT_COUNT: usize = 5;
+
+/// 序号为`index`的候选项所在的分组
+fn group_of(groups: &[(String, usize)], mut index: usize) -> Option<usize> {
+    groups.iter().position(|(_, len)| {
+        if index < *len {
+            true
+        } else {
+            index -= len;
+            false
+        }
+    })
+}
+
+/// 在文本框边缘绘制表示输入无效的错误颜色
+fn paint_invalid(ui: &egui::Ui, rect: egui::Rect) {
+    ui.painter().rect_stroke(
+        rect,
+        ui.visuals().widgets.inactive.corner_radius,
+        Stroke::new(1.0, ui.visuals().error_fg_color),
+        StrokeKind::Inside,
+    );
+}
+
+struct Candidate {
+    /// 在`iter`中的序号
+    index: usize,
+    text: String,
+    ranges: Vec<Range<usize>>,
+    /// 是否显示在最近选择的值中
+    recent: bool,
+}
+
+/// 在文本框处理按键之前消耗掉用于选择候选项的按键。
+///
+/// 上下方向键移动高亮的候选项，返回按下回车或Tab键时高亮的候选项
+fn navigate(
+    ui: &egui::Ui,
+    text_id: Id,
+    popup_id: Id,
+    count: usize,
+    highlight: &mut Option<usize>,
+    scroll_to_highlight: &mut bool,
+) -> Option<usize> {
+    if count == 0 || !ui.memory(|m| m.has_focus(text_id)) {
+        return None;
+    }
+
+    let is_open = Popup::is_id_open(ui.ctx(), popup_id);
+    let (up, down) = ui.input_mut(|i| {
+        (
+            i.consume_key(Modifiers::NONE, Key::ArrowUp),
+            i.consume_key(Modifiers::NONE, Key::ArrowDown),
+        )
+    });
+
+    if up || down {
+        let last = count - 1;
+        *highlight = Some(match *highlight {
+            Some(h) if up => h.checked_sub(1).unwrap_or(last),
+            Some(h) => (h + 1) % count,
+            None if up => last,
+            None => 0,
+        });
+        *scroll_to_highlight = true;
+
+        if !is_open {
+            Popup::open_id(ui.ctx(), popup_id);
+        }
+
+        None
+    } else {
+        highlight.filter(|_| is_open).filter(|_| {
+            ui.input_mut(|i| {
+                i.consume_key(Modifiers::NONE, Key::Enter)
+                    || i.consume_key(Modifiers::NONE, Key::Tab)
+            })
+        })
+    }
+}
+
+/// 将候选项写入文本，并把光标移动到末尾
+fn accept_candidate(ui: &egui::Ui, text_id: Id, text: &mut String, candidate: &str) {
+    candidate.clone_into(text);
+    move_cursor_to_end(ui, text_id, text);
+}
+
+fn move_cursor_to_end(ui: &egui::Ui, text_id: Id, text: &str) {
+    let mut state = TextEditState::load(ui.ctx(), text_id).unwrap_or_default();
+    state
+        .cursor
+        .set_char_range(Some(CCursorRange::one(CCursor::new(text.chars().count()))));
+    state.store(ui.ctx(), text_id);
+}
@@ -0,0 +1,33 @@
+/// 弹出框的最大高度
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PopupHeight {
+    Points(f32),
+    /// 候选项的行数
+    Rows(usize),
+}
+
+/// 弹出框相对于文本框的位置。空间不足时会自动显示在另一侧
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Placement {
+    #[default]
+    Below,
+    Above,
+}
+
+/// 打开弹出框的时机
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OpenOn {
+    /// 点击文本框时打开或关闭
+    #[default]
+    Click,
+    /// 文本框获得焦点时打开
+    Focus,
+    /// 输入文字时打开
+    Typing,
+}
+
+impl Default for PopupHeight {
+    fn default() -> Self {
+        Self::Points(100.0)
+    }
+}
@@ -0,0 +1,25 @@
+use crate::highlight::highlight_text;
+use egui::{Ui, WidgetText};
+use std::ops::Range;
+
+pub type RowUiFn<'a> = Box<dyn Fn(&mut Ui, &CandidateRow) + 'a>;
+
+/// 弹出框中的一个候选项
+#[derive(Debug, Clone, Copy)]
+pub struct CandidateRow<'r> {
+    /// 在`iter`中的序号
+    pub index: usize,
+    /// 选中后写入文本框的文字
+    pub text: &'r str,
+    /// 匹配部分的字节范围
+    pub ranges: &'r [Range<usize>],
+    /// 是否为当前文本框中的值
+    pub selected: bool,
+}
+
+impl CandidateRow<'_> {
+    /// 高亮了匹配部分的候选项文字
+    pub fn label(&self, ui: &Ui) -> WidgetText {
+        highlight_text(ui, self.text, self.ranges)
+    }
+}
@@ -0,0 +1,152 @@
+use super::{Matcher, SelectEdit};
+use egui::{Button, Frame, Id, Key, Margin, Modifiers, Ui, Widget};
+
+/// 从候选项中选择多个标签。
+///
+/// 已选的标签显示为可删除的标签块，输入框为空时按退格键删除最后一个标签
+pub struct TagEdit<'a, S, L>
+where
+    S: ToString,
+    L: Iterator<Item = S>,
+{
+    pub tags: &'a mut Vec<String>,
+    pub iter: L,
+    pub matcher: Matcher<'a>,
+    pub hint_text: Option<&'a str>,
+    /// 是否允许按回车添加不在候选项中的标签
+    pub allow_new: bool,
+}
+
+impl<'a, S, L> TagEdit<'a, S, L>
+where
+    S: ToString,
+    L: Iterator<Item = S>,
+{
+    pub fn new(tags: &'a mut Vec<String>, iter: L) -> Self {
+        Self {
+            tags,
+            iter,
+            matcher: Matcher::IgnoreCase,
+            hint_text: None,
+            allow_new: false,
+        }
+    }
+
+    /// 搜索候选项时使用的匹配方式。默认为[`Matcher::IgnoreCase`]
+    pub fn matcher(self, matcher: Matcher<'a>) -> Self {
+        Self { matcher, ..self }
+    }
+
+    pub fn hint_text(self, hint_text: impl Into<Option<&'a str>>) -> Self {
+        Self {
+            hint_text: hint_text.into(),
+            ..self
+        }
+    }
+
+    pub fn allow_new(self) -> Self {
+        Self {
+            allow_new: true,
+            ..self
+        }
+    }
+}
+
+impl<'a, S, L> Widget for TagEdit<'a, S, L>
+where
+    S: ToString,
+    L: Iterator<Item = S>,
+{
+    fn ui(self, ui: &mut Ui) -> egui::Response {
+        let TagEdit {
+            tags,
+            iter,
+            matcher,
+            hint_text,
+            allow_new,
+        } = self;
+
+        let mut resp = ui.horizontal_wrapped(|ui| {
+            let input_id = ui.auto_id_with(module_path!()).with("input");
+            let mut input: String = ui.data(|d| d.get_temp(input_id)).unwrap_or_default();
+            let mut changed = false;
+
+            let mut remove = None;
+            for (idx, tag) in tags.iter().enumerate() {
+                if ui_tag(ui, tag) {
+                    remove = Some(idx);
+                }
+            }
+            if let Some(idx) = remove {
+                tags.remove(idx);
+                changed = true;
+            }
+
+            let labels = iter
+                .map(|item| item.to_string())
+                .filter(|label| !tags.contains(label))
+                .collect::<Vec<_>>();
+
+            // 标签块会占用自动生成的id，使用固定的id使增删标签时文本框不会失去焦点。
+            // 与`SelectEdit`中文本框的id一致
+            let text_id = ui.make_persistent_id(Id::new(input_id));
+            if input.is_empty()
+                && ui.memory(|m| m.has_focus(text_id))
+                && ui.input_mut(|i| i.consume_key(Modifiers::NONE, Key::Backspace))
+            {
+                changed = tags.pop().is_some() || changed;
+            }
+
+            let resp = SelectEdit::new(&mut input, labels.iter())
+                .matcher(matcher)
+                .hint_text(hint_text)
+                .id_salt(input_id)
+                .show(ui);
+            let (picked, resp) = (resp.inner.picked, resp.response);
+
+            if let Some(idx) = picked {
+                tags.push(labels[idx].clone());
+                input.clear();
+                changed = true;
+            } else if allow_new
+                && resp.lost_focus()
+                && ui.input(|i| i.key_pressed(Key::Enter))
+                && !input.trim().is_empty()
+            {
+                let tag = input.trim().to_string();
+                if !tags.contains(&tag) {
+                    tags.push(tag);
+                    changed = true;
+                }
+                input.clear();
+                resp.request_focus();
+            }
+
+            ui.data_mut(|d| d.insert_temp(input_id, input));
+
+            changed
+        });
+
+        if resp.inner {
+            resp.response.mark_changed();
+        }
+
+        resp.response
+    }
+}
+
+/// 显示一个标签块，返回是否点击了删除按钮
+fn ui_tag(ui: &mut Ui, tag: &str) -> bool {
+    let visuals = &ui.visuals().widgets.inactive;
+
+    Frame::new()
+        .fill(visuals.bg_fill)
+        .corner_radius(visuals.corner_radius)
+        .inner_margin(Margin::symmetric(4, 1))
+        .show(ui, |ui| {
+            ui.spacing_mut().item_spacing.x = 2.0;
+            ui.label(tag);
+            ui.add(Button::new("×").frame(false).small()).clicked()
+        })
+        .inner
+}
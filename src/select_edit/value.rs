@@ -0,0 +1,104 @@
+use super::{Matcher, SelectEdit};
+use egui::{response::Flags, Widget};
+
+/// 从候选项中选择一个值。
+///
+/// 与[`SelectEdit`]不同，输入的文字只用于搜索，保存在egui的内存中，
+/// 只有在选中候选项时才会修改`value`
+pub struct SelectValue<'a, T, L, D>
+where
+    T: PartialEq,
+    L: Iterator<Item = T>,
+    D: Fn(&T) -> String,
+{
+    pub value: &'a mut T,
+    pub iter: L,
+    /// 值的显示文字
+    pub display: D,
+    pub matcher: Matcher<'a>,
+    pub hint_text: Option<&'a str>,
+}
+
+impl<'a, T, L, D> SelectValue<'a, T, L, D>
+where
+    T: PartialEq,
+    L: Iterator<Item = T>,
+    D: Fn(&T) -> String,
+{
+    pub fn new(value: &'a mut T, iter: L, display: D) -> Self {
+        Self {
+            value,
+            iter,
+            display,
+            matcher: Matcher::IgnoreCase,
+            hint_text: None,
+        }
+    }
+
+    /// 搜索候选项时使用的匹配方式。默认为[`Matcher::IgnoreCase`]
+    pub fn matcher(self, matcher: Matcher<'a>) -> Self {
+        Self { matcher, ..self }
+    }
+
+    pub fn hint_text(self, hint_text: impl Into<Option<&'a str>>) -> Self {
+        Self {
+            hint_text: hint_text.into(),
+            ..self
+        }
+    }
+}
+
+impl<'a, T, L, D> Widget for SelectValue<'a, T, L, D>
+where
+    T: PartialEq,
+    L: Iterator<Item = T>,
+    D: Fn(&T) -> String,
+{
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        let SelectValue {
+            value,
+            iter,
+            display,
+            matcher,
+            hint_text,
+        } = self;
+
+        let search_id = ui.auto_id_with(module_path!()).with("search");
+
+        let items = iter.collect::<Vec<_>>();
+        let labels = items.iter().map(&display).collect::<Vec<_>>();
+        let current = display(value);
+
+        // 没有正在编辑的搜索文字时显示当前值
+        let mut search: String = ui
+            .data(|d| d.get_temp(search_id))
+            .unwrap_or_else(|| current.clone());
+
+        let filter = search != current;
+        let resp = SelectEdit {
+            filter,
+            ..SelectEdit::new(&mut search, labels.iter())
+                .matcher(matcher)
+                .hint_text(hint_text)
+        }
+        .show(ui);
+        let (picked, mut resp) = (resp.inner.picked, resp.response);
+
+        let mut changed = false;
+        if let Some(item) = picked.and_then(|idx| items.into_iter().nth(idx)) {
+            if *value != item {
+                *value = item;
+                changed = true;
+            }
+        }
+
+        if resp.has_focus() && picked.is_none() {
+            ui.data_mut(|d| d.insert_temp(search_id, search));
+        } else {
+            ui.data_mut(|d| d.remove::<String>(search_id));
+        }
+
+        resp.flags.set(Flags::CHANGED, changed);
+        resp
+    }
+}